@@ -165,7 +165,20 @@ export const reviewSubmission = async (req: AuthenticatedRequest, res: Response)
           const onChainChallengeId = submission.challenge.id.slice(0, 32);
           const userPubkey = new PublicKey(submission.user.walletAddress);
 
-          onChainTx = await solanaProgram.recordProof(onChainChallengeId, userPubkey);
+          const onChainChallenge = await solanaProgram.getChallenge(onChainChallengeId);
+          if (!onChainChallenge) {
+            throw new Error(`Challenge ${onChainChallengeId} not found on-chain`);
+          }
+          // The proof covers the challenge day the submission was made on
+          const dayIndex = solanaProgram.getDayIndex(onChainChallenge, submission.submissionDate);
+          const proofHash = solanaProgram.hashProof(submission.id, submission.imageUrl);
+
+          onChainTx = await solanaProgram.recordProof(
+            onChainChallengeId,
+            userPubkey,
+            dayIndex,
+            proofHash
+          );
           logger.info(`Proof recorded on-chain for user ${submission.user.id}, tx: ${onChainTx}`);
         }
       } catch (onChainError: any) {
@@ -11,10 +11,11 @@
  *   - ORACLE_KEYPAIR_PATH or ORACLE_KEYPAIR_JSON: Path to or JSON of the oracle/authority keypair
  *   - TREASURY_ADDRESS: Solana address to receive forfeited stakes
  *   - SOLANA_RPC_URL: RPC endpoint (defaults to devnet)
+ *   - FACTORY_UPDATE_DELAY_SECONDS: Timelock on settings updates and role transfers (defaults to 1 day)
  */
 
 import { Connection, Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
-import { Program, AnchorProvider, Wallet } from '@coral-xyz/anchor';
+import { Program, AnchorProvider, Wallet, BN } from '@coral-xyz/anchor';
 import * as fs from 'fs';
 import * as path from 'path';
 import dotenv from 'dotenv';
@@ -28,6 +29,7 @@ const idlJson = JSON.parse(fs.readFileSync(idlPath, 'utf-8'));
 
 const PROGRAM_ID = new PublicKey(idlJson.address);
 const RPC_ENDPOINT = process.env.SOLANA_RPC_URL || 'https://api.devnet.solana.com';
+const UPDATE_DELAY_SECONDS = Number(process.env.FACTORY_UPDATE_DELAY_SECONDS || 24 * 60 * 60);
 
 async function main() {
   console.log('='.repeat(60));
@@ -157,11 +159,12 @@ async function main() {
 
   // 8. Initialize factory
   console.log('8. Initializing factory...');
+  console.log(`   Update delay: ${UPDATE_DELAY_SECONDS}s`);
   console.log('   Sending transaction...');
 
   try {
     const tx = await program.methods
-      .initializeFactory()
+      .initializeFactory(new BN(UPDATE_DELAY_SECONDS))
       .accountsPartial({
         authority: authorityKeypair.publicKey,
         treasury: treasuryPubkey,
@@ -2,6 +2,7 @@ import { Connection, Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
 import { Program, AnchorProvider, Wallet, BN, Idl } from '@coral-xyz/anchor';
 import { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID, getAssociatedTokenAddress } from '@solana/spl-token';
 import * as fs from 'fs';
+import { createHash } from 'crypto';
 
 // Load IDL
 const idlJson = require('../idl/proven_stake.json');
@@ -22,8 +23,60 @@ export enum ChallengeStatus {
   Ended = 'ended',
   Settled = 'settled',
   Cancelled = 'cancelled',
+  Paused = 'paused',
 }
 
+/**
+ * Payout mode enum matching on-chain
+ */
+export type PayoutMode = { threshold: {} } | { daily: {} } | { proportional: {} };
+
+/**
+ * Completion bracket: winners at or above `thresholdBps` share the bonus pool by `weight`
+ */
+export interface RewardBracket {
+  thresholdBps: number;
+  weight: number;
+}
+
+/**
+ * Bracket outcome, computed at settlement
+ */
+export interface BracketPayout {
+  winnerCount: number;
+  bonusPerWinner: BN;
+  remainder: BN;
+  winnerStakes: BN;
+}
+
+/**
+ * Challenge rules passed to `create_challenge`
+ */
+export interface CreateChallengeParams {
+  payoutMode: PayoutMode;
+  thresholdBps: number;
+  creatorFeeBps: number;
+  rewardBrackets: RewardBracket[];
+  earlyExitPenaltyBps: number;
+  lateJoinDays: number;
+  maxParticipants: number;
+  minParticipants: number;
+}
+
+/**
+ * Rules used for backend-created challenges: 80% threshold, no fees, caps or brackets
+ */
+export const DEFAULT_CHALLENGE_PARAMS: CreateChallengeParams = {
+  payoutMode: { threshold: {} },
+  thresholdBps: 8000,
+  creatorFeeBps: 0,
+  rewardBrackets: [],
+  earlyExitPenaltyBps: 0,
+  lateJoinDays: 0,
+  maxParticipants: 0,
+  minParticipants: 0,
+};
+
 /**
  * On-chain Challenge account data
  */
@@ -39,15 +92,49 @@ export interface ChallengeAccount {
   status: ChallengeStatus;
   startTs: BN;
   endTs: BN;
+  pausedAt: BN;
+  resumeStatus: ChallengeStatus;
   participantCount: number;
   activeParticipants: number;
+  maxParticipants: number;
+  minParticipants: number;
   winnerCount: number;
   loserCount: number;
   bonusPerWinner: BN;
   forfeitedAmount: BN;
   remainder: BN;
   payoutsClaimedCount: number;
-  remainderClaimed: BN;
+  dayLengthSeconds: BN;
+  payoutMode: PayoutMode;
+  daysSettled: number;
+  dailyReleased: BN;
+  dailyClaimed: BN;
+  disputeWindowSeconds: BN;
+  claimWindowSeconds: BN;
+  claimDeadline: BN;
+  openDisputes: number;
+  oracle: PublicKey;
+  oracleCommittee: PublicKey[];
+  oracleThreshold: number;
+  treasury: PublicKey;
+  feeBps: number;
+  feeAmount: BN;
+  creatorFeeBps: number;
+  creatorFeeAmount: BN;
+  sponsorPool: BN;
+  earlyExitPenaltyBps: number;
+  penaltyPool: BN;
+  lateJoinDays: number;
+  totalStakes: BN;
+  losersStakes: BN;
+  winnerStakes: BN;
+  totalProofDays: BN;
+  unearnedStakes: BN;
+  bonusPool: BN;
+  bonusClaimed: BN;
+  rewardBrackets: RewardBracket[];
+  bracketPayouts: BracketPayout[];
+  dailyProofCounts: number[];
   bump: number;
 }
 
@@ -59,11 +146,22 @@ export interface ParticipantAccount {
   challenge: PublicKey;
   joined: boolean;
   stakeDeposited: BN;
+  joinedDay: number;
   proofDays: number;
   isWinner: boolean;
   isSettled: boolean;
+  bracket: number;
+  poolOffset: BN;
+  payoutAmount: BN;
+  payoutAssigned: boolean;
   payoutClaimed: boolean;
   refundClaimed: boolean;
+  swept: boolean;
+  forfeited: boolean;
+  dailyClaimCursor: number;
+  openDisputes: number;
+  revocationCount: number;
+  proofBitmap: Buffer;
   bump: number;
 }
 
@@ -75,6 +173,16 @@ export interface FactoryAccount {
   treasury: PublicKey;
   oracle: PublicKey;
   challengeCount: BN;
+  dayLengthSeconds: BN;
+  disputeWindowSeconds: BN;
+  claimWindowSeconds: BN;
+  oracleCommittee: PublicKey[];
+  oracleThreshold: number;
+  updateDelaySeconds: BN;
+  minThresholdBps: number;
+  maxThresholdBps: number;
+  feeBps: number;
+  maxCreatorFeeBps: number;
   bump: number;
 }
 
@@ -178,23 +286,37 @@ class SolanaProgramService {
     );
   }
 
+  /**
+   * Derive the proof record PDA for a participant-day
+   */
+  getProofRecordPDA(participantPubkey: PublicKey, dayIndex: number): [PublicKey, number] {
+    const dayBuf = Buffer.alloc(4);
+    dayBuf.writeUInt32LE(dayIndex);
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('proof'), participantPubkey.toBuffer(), dayBuf],
+      PROGRAM_ID
+    );
+  }
+
   // ============================================================
   // FACTORY OPERATIONS
   // ============================================================
 
   /**
    * Initialize the factory (one-time setup)
+   * `updateDelaySeconds` is the timelock on later settings updates and role transfers.
    */
   async initializeFactory(
     authorityKeypair: Keypair,
     treasuryPubkey: PublicKey,
-    oraclePubkey: PublicKey
+    oraclePubkey: PublicKey,
+    updateDelaySeconds: number
   ): Promise<string> {
     const program = this.getProgram();
     const [factoryPDA] = this.getFactoryPDA();
 
     const tx = await program.methods
-      .initializeFactory()
+      .initializeFactory(new BN(updateDelaySeconds))
       .accountsPartial({
         authority: authorityKeypair.publicKey,
         treasury: treasuryPubkey,
@@ -234,7 +356,8 @@ class SolanaProgramService {
     challengeId: string,
     stakeAmount: number,
     totalDays: number,
-    startTs: number
+    startTs: number,
+    params: CreateChallengeParams = DEFAULT_CHALLENGE_PARAMS
   ): Promise<{ signature: string; challengePDA: PublicKey; escrowVault: PublicKey }> {
     const program = this.getProgram();
     const [factoryPDA] = this.getFactoryPDA();
@@ -247,7 +370,7 @@ class SolanaProgramService {
     const stakeAmountBN = new BN(Math.floor(stakeAmount * 1_000_000));
 
     const tx = await program.methods
-      .createChallenge(challengeId, stakeAmountBN, totalDays, new BN(startTs))
+      .createChallenge(challengeId, stakeAmountBN, totalDays, new BN(startTs), params)
       .accountsPartial({
         creator: creatorKeypair.publicKey,
         factory: factoryPDA,
@@ -329,9 +452,15 @@ class SolanaProgramService {
   // ============================================================
 
   /**
-   * Record a proof for a participant (oracle only)
+   * Record a proof for a participant-day (oracle only)
+   * `proofHash` commits to the approved submission, see `hashProof`.
    */
-  async recordProof(challengeId: string, userPubkey: PublicKey): Promise<string> {
+  async recordProof(
+    challengeId: string,
+    userPubkey: PublicKey,
+    dayIndex: number,
+    proofHash: number[]
+  ): Promise<string> {
     if (!this.oracleKeypair) {
       throw new Error('Oracle keypair not configured');
     }
@@ -340,14 +469,17 @@ class SolanaProgramService {
     const [factoryPDA] = this.getFactoryPDA();
     const [challengePDA] = this.getChallengePDA(challengeId, factoryPDA);
     const [participantPDA] = this.getParticipantPDA(challengePDA, userPubkey);
+    const [proofRecordPDA] = this.getProofRecordPDA(participantPDA, dayIndex);
 
     const tx = await program.methods
-      .recordProof(challengeId)
+      .recordProof(challengeId, dayIndex, proofHash)
       .accountsPartial({
         oracle: this.oracleKeypair.publicKey,
         factory: factoryPDA,
         challenge: challengePDA,
         participant: participantPDA,
+        proofRecord: proofRecordPDA,
+        systemProgram: SystemProgram.programId,
       })
       .signers([this.oracleKeypair])
       .rpc();
@@ -458,6 +590,21 @@ class SolanaProgramService {
     return accounts.map((a: any) => a.account as ParticipantAccount);
   }
 
+  /**
+   * Challenge day (0-based) a timestamp falls in, using the challenge's on-chain clock
+   */
+  getDayIndex(challenge: ChallengeAccount, at: Date): number {
+    const elapsed = Math.floor(at.getTime() / 1000) - challenge.startTs.toNumber();
+    return Math.max(0, Math.floor(elapsed / challenge.dayLengthSeconds.toNumber()));
+  }
+
+  /**
+   * Hash committing to an approved submission, stored in its on-chain proof record
+   */
+  hashProof(submissionId: string, imageUrl: string): number[] {
+    return Array.from(createHash('sha256').update(`${submissionId}:${imageUrl}`).digest());
+  }
+
   /**
    * Convert USDC amount from on-chain (with 6 decimals) to display amount
    */
@@ -1,18 +1,346 @@
 #![allow(unexpected_cfgs)]
 
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::sysvar::instructions::load_instruction_at_checked;
 use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
 
 declare_id!("8UMUh8uz4QAd5K5j4vSB5fGXTfCngJc4Gf1YJuydT9qw");
 
-/// Win threshold: 80% of days (8000 basis points)
+/// Standard win threshold: 80% of days (8000 basis points)
 pub const WIN_THRESHOLD_BPS: u16 = 8000;
 
+/// Default bounds for a challenge's `threshold_bps`.
+/// Stored on the factory so difficulty levels can be tuned without changing code.
+pub const DEFAULT_MIN_THRESHOLD_BPS: u16 = 5000;
+pub const DEFAULT_MAX_THRESHOLD_BPS: u16 = 10000;
+
+/// Upper bound for the factory's platform fee on losers' stakes (10%)
+pub const MAX_FEE_BPS: u16 = 1000;
+
+/// Upper bound for the factory's cap on creator fees (20%)
+pub const MAX_CREATOR_FEE_BPS: u16 = 2000;
+
+/// Default cap on the fee a creator can set on forfeited stakes (5%)
+pub const DEFAULT_MAX_CREATOR_FEE_BPS: u16 = 500;
+
 /// Default "day length" in seconds used to compute `end_ts`.
 /// Stored on the factory so tests/localnet can shorten it without changing code.
 pub const DEFAULT_DAY_LENGTH_SECONDS: i64 = 24 * 60 * 60;
 
+/// Default time after `end_ts` during which users can contest missing proofs
+/// and participants cannot be settled.
+pub const DEFAULT_DISPUTE_WINDOW_SECONDS: i64 = 24 * 60 * 60;
+
+/// Default time after settlement or cancellation during which payouts and
+/// refunds can be claimed before the treasury may sweep them.
+pub const DEFAULT_CLAIM_WINDOW_SECONDS: i64 = 90 * 24 * 60 * 60;
+
+/// Longest allowed challenge. Per-day state (proof bitmaps, daily counters)
+/// is sized from `total_days`, so this keeps those accounts bounded.
+pub const MAX_TOTAL_DAYS: u32 = 366;
+
+/// Domain prefix of oracle attestation messages, so they can't be replayed as
+/// signatures for anything else
+pub const ATTESTATION_DOMAIN: &[u8] = b"proven-stake:proof-attestation:v2";
+
+/// Maximum number of extra oracles on the factory's committee
+pub const MAX_ORACLE_COMMITTEE: usize = 5;
+
+/// Highest early-exit penalty a creator may set (basis points of the stake)
+pub const MAX_EARLY_EXIT_PENALTY_BPS: u16 = 5000;
+
+/// Maximum number of reward brackets on a `Threshold` mode challenge
+pub const MAX_REWARD_BRACKETS: usize = 4;
+
+/// Committee members must be distinct, exclude the primary oracle, and leave
+/// `threshold` reachable
+fn validate_oracle_committee(oracle: &Pubkey, members: &[Pubkey], threshold: u8) -> Result<()> {
+    require!(
+        members.len() <= MAX_ORACLE_COMMITTEE,
+        ProvenError::InvalidOracleCommittee
+    );
+    for (i, member) in members.iter().enumerate() {
+        require!(
+            member != oracle && !members[..i].contains(member),
+            ProvenError::InvalidOracleCommittee
+        );
+    }
+    require!(
+        threshold >= 1 && threshold as usize <= members.len() + 1,
+        ProvenError::InvalidOracleThreshold
+    );
+    Ok(())
+}
+
+/// Stores `update` as the factory's single pending update, due after the
+/// factory's `update_delay_seconds`
+fn queue_factory_update(
+    factory: &mut EscrowFactory,
+    mut update: PendingFactoryUpdate,
+) -> Result<()> {
+    require!(
+        factory.pending_update.is_none(),
+        ProvenError::UpdateAlreadyQueued
+    );
+
+    update.eta = Clock::get()?
+        .unix_timestamp
+        .checked_add(factory.update_delay_seconds)
+        .ok_or(ProvenError::MathOverflow)?;
+
+    emit!(FactoryUpdateQueued {
+        day_length_seconds: update.day_length_seconds,
+        dispute_window_seconds: update.dispute_window_seconds,
+        claim_window_seconds: update.claim_window_seconds,
+        update_delay_seconds: update.update_delay_seconds,
+        min_threshold_bps: update.min_threshold_bps,
+        max_threshold_bps: update.max_threshold_bps,
+        fee_bps: update.fee_bps,
+        max_creator_fee_bps: update.max_creator_fee_bps,
+        oracle_committee: update.oracle_committee.clone(),
+        oracle_threshold: update.oracle_threshold,
+        eta: update.eta,
+    });
+
+    factory.pending_update = Some(update);
+    Ok(())
+}
+
+/// Requires the challenge's `oracle_threshold` distinct oracles to have signed:
+/// the `oracle` account plus any additional committee signers passed as
+/// remaining accounts.
+fn require_oracle_quorum(
+    challenge: &ChallengeEscrow,
+    oracle: &Signer,
+    remaining_accounts: &[AccountInfo],
+) -> Result<()> {
+    require!(challenge.is_oracle(&oracle.key()), ProvenError::InvalidOracle);
+
+    let mut approvals = vec![oracle.key()];
+    for info in remaining_accounts {
+        if info.is_signer && challenge.is_oracle(info.key) && !approvals.contains(info.key) {
+            approvals.push(*info.key);
+        }
+    }
+
+    require!(
+        approvals.len() >= challenge.oracle_threshold as usize,
+        ProvenError::OracleQuorumNotMet
+    );
+    Ok(())
+}
+
+/// Whether `accounts` include the participant's open `Dispute` for `day_index`
+/// Disputes are closed on resolution, so an initialized one is still open.
+fn has_open_dispute(participant: &Pubkey, day_index: u32, accounts: &[AccountInfo]) -> bool {
+    accounts.iter().any(|info| {
+        info.owner == &crate::ID
+            && info.try_borrow_data().is_ok_and(|data| {
+                Dispute::try_deserialize(&mut &data[..]).is_ok_and(|dispute| {
+                    dispute.participant == *participant && dispute.day_index == day_index
+                })
+            })
+    })
+}
+
+/// Shared by `record_proof` and `submit_attested_proof` once oracle approval
+/// has been verified: validates the day and marks it proven.
+/// Past the dispute window the day's open `Dispute` must be in `remaining_accounts`.
+#[allow(clippy::too_many_arguments)]
+fn apply_proof<'info>(
+    challenge: &mut Account<'info, ChallengeEscrow>,
+    participant: &mut Account<'info, Participant>,
+    proof_record: &mut Account<'info, ProofRecord>,
+    remaining_accounts: &[AccountInfo],
+    day_index: u32,
+    proof_hash: [u8; 32],
+    payer: Pubkey,
+    bump: u8,
+) -> Result<()> {
+    let clock = Clock::get()?;
+
+    require!(
+        challenge.status != ChallengeStatus::Paused,
+        ProvenError::ChallengePaused
+    );
+    require!(
+        challenge.status == ChallengeStatus::Created
+            || challenge.status == ChallengeStatus::Started
+            || challenge.status == ChallengeStatus::Ended,
+        ProvenError::InvalidChallengeStatus
+    );
+    require!(
+        clock.unix_timestamp >= challenge.start_ts,
+        ProvenError::ChallengeNotStarted
+    );
+    // An undersubscribed challenge stays cancellable, so it must not start
+    require!(
+        !challenge.undersubscribed(),
+        ProvenError::MinParticipantsNotMet
+    );
+    // Late proofs are accepted through the dispute window, and after it
+    // only for a day the participant still has a dispute open for
+    require!(
+        clock.unix_timestamp <= challenge.dispute_end_ts()
+            || has_open_dispute(&participant.key(), day_index, remaining_accounts),
+        ProvenError::ChallengeEnded
+    );
+    require!(participant.joined, ProvenError::NotJoined);
+    require!(!participant.is_settled, ProvenError::AlreadySettled);
+    // Prevent recording more proofs than total days
+    require!(
+        participant.proof_days < challenge.total_days,
+        ProvenError::MaxProofsReached
+    );
+
+    // Day must be inside the participant's window and already begun
+    require!(
+        day_index >= participant.joined_day
+            && day_index < challenge.total_days
+            && day_index <= challenge.day_index_at(clock.unix_timestamp),
+        ProvenError::DayOutOfRange
+    );
+    require!(
+        !participant.has_proof_for(day_index),
+        ProvenError::ProofAlreadyRecorded
+    );
+    // Settled days are final in daily mode
+    if challenge.payout_mode == PayoutMode::Daily {
+        require!(
+            day_index >= challenge.days_settled,
+            ProvenError::DayAlreadySettled
+        );
+    }
+
+    // Auto-start challenge on first proof
+    if challenge.status == ChallengeStatus::Created {
+        challenge.status = ChallengeStatus::Started;
+    }
+
+    // Mark the day and increment proof days for participant
+    participant.set_proof(day_index, true);
+    participant.proof_days += 1;
+    challenge.daily_proof_counts[day_index as usize] += 1;
+
+    proof_record.challenge = challenge.key();
+    proof_record.participant = participant.key();
+    proof_record.user = participant.user;
+    proof_record.day_index = day_index;
+    proof_record.proof_hash = proof_hash;
+    proof_record.recorded_at = clock.unix_timestamp;
+    proof_record.payer = payer;
+    proof_record.bump = bump;
+
+    emit!(ProofRecorded {
+        challenge_id: challenge.key(),
+        user: participant.user,
+        day_index,
+        proof_hash,
+        proof_days: participant.proof_days,
+        total_required: required_days(challenge.total_days, challenge.threshold_bps),
+    });
+
+    Ok(())
+}
+
+/// Message an oracle signs off-chain to approve a proof for `submit_attested_proof`
+/// `revocation_count` is the participant's current count, so attestations signed
+/// before a `revoke_proof` can't be resubmitted to undo it.
+pub fn attestation_message(
+    challenge: &Pubkey,
+    user: &Pubkey,
+    day_index: u32,
+    proof_hash: &[u8; 32],
+    revocation_count: u32,
+    expires_at: i64,
+) -> Vec<u8> {
+    let mut message = Vec::with_capacity(ATTESTATION_DOMAIN.len() + 32 + 32 + 4 + 32 + 4 + 8);
+    message.extend_from_slice(ATTESTATION_DOMAIN);
+    message.extend_from_slice(challenge.as_ref());
+    message.extend_from_slice(user.as_ref());
+    message.extend_from_slice(&day_index.to_le_bytes());
+    message.extend_from_slice(proof_hash);
+    message.extend_from_slice(&revocation_count.to_le_bytes());
+    message.extend_from_slice(&expires_at.to_le_bytes());
+    message
+}
+
+/// Requires the challenge's `oracle_threshold` distinct oracles to have signed
+/// `message` through Ed25519 precompile instructions in the current transaction.
+/// Only signatures whose key, signature and message live in the precompile
+/// instruction's own data are counted.
+fn require_oracle_attestations(
+    challenge: &ChallengeEscrow,
+    instructions: &AccountInfo,
+    message: &[u8],
+) -> Result<()> {
+    let mut signers: Vec<Pubkey> = Vec::new();
+    let mut index = 0;
+    while let Ok(ix) = load_instruction_at_checked(index, instructions) {
+        index += 1;
+        if ix.program_id != ed25519_program::ID {
+            continue;
+        }
+        let data = &ix.data;
+        let count = *data.first().ok_or(ProvenError::InvalidAttestation)? as usize;
+        for i in 0..count {
+            // Ed25519SignatureOffsets: seven u16s after the 2-byte header
+            let start = 2 + i * 14;
+            let offsets = data
+                .get(start..start + 14)
+                .ok_or(ProvenError::InvalidAttestation)?;
+            let read = |at: usize| u16::from_le_bytes([offsets[at], offsets[at + 1]]);
+            let (pubkey_offset, message_offset, message_size) =
+                (read(4) as usize, read(8) as usize, read(10) as usize);
+            // Signature, key and message must come from this instruction
+            if read(2) != u16::MAX || read(6) != u16::MAX || read(12) != u16::MAX {
+                continue;
+            }
+            let pubkey = data
+                .get(pubkey_offset..pubkey_offset + 32)
+                .ok_or(ProvenError::InvalidAttestation)?;
+            let signed = data
+                .get(message_offset..message_offset + message_size)
+                .ok_or(ProvenError::InvalidAttestation)?;
+            let pubkey = Pubkey::try_from(pubkey).map_err(|_| ProvenError::InvalidAttestation)?;
+            if signed == message && challenge.is_oracle(&pubkey) && !signers.contains(&pubkey) {
+                signers.push(pubkey);
+            }
+        }
+    }
+
+    require!(!signers.is_empty(), ProvenError::InvalidAttestation);
+    require!(
+        signers.len() >= challenge.oracle_threshold as usize,
+        ProvenError::OracleQuorumNotMet
+    );
+    Ok(())
+}
+
+/// Brackets must start at the win threshold, rise strictly, and carry a weight
+fn validate_reward_brackets(brackets: &[RewardBracket], threshold_bps: u16) -> Result<()> {
+    require!(
+        brackets.len() <= MAX_REWARD_BRACKETS,
+        ProvenError::InvalidRewardBrackets
+    );
+    if let Some(lowest) = brackets.first() {
+        require!(
+            lowest.threshold_bps == threshold_bps,
+            ProvenError::InvalidRewardBrackets
+        );
+    }
+    require!(
+        brackets.iter().all(|b| b.weight > 0 && b.threshold_bps <= 10000)
+            && brackets
+                .windows(2)
+                .all(|pair| pair[0].threshold_bps < pair[1].threshold_bps),
+        ProvenError::InvalidRewardBrackets
+    );
+    Ok(())
+}
+
 fn required_days(total_days: u32, threshold_bps: u16) -> u32 {
     // Ceil(total_days * threshold_bps / 10000).
     // This avoids the bug where `total_days=1` and `threshold_bps=8000` would floor to 0.
@@ -22,6 +350,180 @@ fn required_days(total_days: u32, threshold_bps: u16) -> u32 {
     (numerator / 10000) as u32
 }
 
+/// Fee taken from a pool of forfeited stakes, rounded down
+fn fee_share(pool: u64, fee_bps: u16) -> u64 {
+    (pool as u128 * fee_bps as u128 / 10000) as u64
+}
+
+/// `amount * weight / total_weight`, rounded down
+fn weighted_share(amount: u64, weight: u64, total_weight: u64) -> u64 {
+    if total_weight == 0 {
+        return 0;
+    }
+    (amount as u128 * weight as u128 / total_weight as u128) as u64
+}
+
+/// A winner's slice of `pool`: the share for weights `[offset, offset + weight)`
+/// out of `total_weight`. Consecutive slices sum to exactly `pool`, so rounding
+/// dust lands on fixed positions instead of on whoever claims first.
+fn pool_slice(pool: u64, offset: u64, weight: u64, total_weight: u64) -> u64 {
+    weighted_share(pool, offset + weight, total_weight) - weighted_share(pool, offset, total_weight)
+}
+
+/// Stake a settled winner gets back: all of it, or in `Proportional` mode the
+/// part earned by proof days over the participant's own window
+fn returned_stake(challenge: &ChallengeEscrow, participant: &Participant) -> u64 {
+    if challenge.payout_mode == PayoutMode::Proportional {
+        weighted_share(
+            participant.stake_deposited,
+            participant.proof_days as u64,
+            participant.window_days(challenge.total_days) as u64,
+        )
+    } else {
+        participant.stake_deposited
+    }
+}
+
+/// What a settled participant is owed once the challenge is settled: returned
+/// stake plus their slice of the bonus pool for winners, nothing for losers
+fn payout_entitlement(challenge: &ChallengeEscrow, participant: &Participant) -> Result<u64> {
+    if !participant.is_winner {
+        return Ok(0);
+    }
+
+    let bonus = if challenge.payout_mode == PayoutMode::Proportional {
+        // Proof-day-weighted slice of the pool
+        pool_slice(
+            challenge.bonus_pool,
+            participant.pool_offset,
+            participant.proof_days as u64,
+            challenge.total_proof_days,
+        )
+    } else if !challenge.reward_brackets.is_empty() {
+        // Stake-weighted slice of the bracket's pool, so late joiners' prorated
+        // stakes earn a prorated bonus
+        let payout = &challenge.bracket_payouts[participant.bracket as usize];
+        let pool = payout.bonus_per_winner * payout.winner_count as u64 + payout.remainder;
+        pool_slice(
+            pool,
+            participant.pool_offset,
+            participant.stake_deposited,
+            payout.winner_stakes,
+        )
+    } else {
+        // Stake-weighted slice of the pool
+        pool_slice(
+            challenge.bonus_pool,
+            participant.pool_offset,
+            participant.stake_deposited,
+            challenge.winner_stakes,
+        )
+    };
+
+    returned_stake(challenge, participant)
+        .checked_add(bonus)
+        .ok_or(ProvenError::MathOverflow.into())
+}
+
+/// Marks a winner's recorded payout as paid out and updates the challenge's
+/// payout counters. Returns the payout split into returned stake and bonus.
+fn mark_payout_paid(
+    challenge: &mut ChallengeEscrow,
+    participant: &mut Participant,
+) -> Result<(u64, u64)> {
+    let stake_returned = returned_stake(challenge, participant);
+    let bonus = participant.payout_amount - stake_returned;
+
+    participant.payout_claimed = true;
+    challenge.payouts_claimed_count = challenge
+        .payouts_claimed_count
+        .checked_add(1)
+        .ok_or(ProvenError::MathOverflow)?;
+    challenge.bonus_claimed = challenge
+        .bonus_claimed
+        .checked_add(bonus)
+        .ok_or(ProvenError::MathOverflow)?;
+
+    Ok((stake_returned, bonus))
+}
+
+/// Pays a settled winner their recorded entitlement from the escrow vault into
+/// `destination`, shared by `claim_payout` and `distribute_payout`
+fn pay_out_winner<'info>(
+    challenge: &mut Account<'info, ChallengeEscrow>,
+    participant: &mut Account<'info, Participant>,
+    escrow_vault: &Account<'info, TokenAccount>,
+    destination: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+) -> Result<()> {
+    require!(
+        challenge.status == ChallengeStatus::Settled,
+        ProvenError::ChallengeNotSettled
+    );
+    require!(participant.is_settled, ProvenError::NotSettled);
+    require!(participant.is_winner, ProvenError::NotWinner);
+    require!(
+        !participant.payout_claimed,
+        ProvenError::PayoutAlreadyClaimed
+    );
+    require!(
+        challenge.payouts_claimed_count < challenge.winner_count,
+        ProvenError::AllPayoutsClaimed
+    );
+
+    require!(participant.payout_assigned, ProvenError::PayoutNotAssigned);
+
+    // Pay the entitlement recorded by `assign_payouts` (returned stake + bonus)
+    let payout_amount = participant.payout_amount;
+
+    // Store values for PDA signer and event
+    let challenge_id_str = challenge.challenge_id.clone();
+    let factory_key = challenge.factory;
+    let bump = challenge.bump;
+    let user_pubkey = participant.user;
+
+    let seeds = &[
+        b"challenge",
+        challenge_id_str.as_bytes(),
+        factory_key.as_ref(),
+        &[bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    // Transfer tokens from escrow to winner
+    let cpi_accounts = Transfer {
+        from: escrow_vault.to_account_info(),
+        to: destination.to_account_info(),
+        authority: challenge.to_account_info(),
+    };
+    let cpi_program = token_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+    token::transfer(cpi_ctx, payout_amount)?;
+
+    // Update state
+    let (stake_returned, bonus) = mark_payout_paid(challenge, participant)?;
+
+    emit!(PayoutClaimed {
+        challenge_id: challenge.key(),
+        user: user_pubkey,
+        stake_returned,
+        bonus_received: bonus,
+        total_amount: payout_amount,
+    });
+
+    Ok(())
+}
+
+/// Stake released for a single day in `Daily` payout mode.
+/// Splits `stake_amount` across days as floor((d+1)*S/T) - floor(d*S/T),
+/// so the per-day amounts always sum to exactly the stake (no dust left behind).
+fn daily_base_amount(stake_amount: u64, total_days: u32, day_index: u32) -> u64 {
+    let stake = stake_amount as u128;
+    let total = total_days as u128;
+    let upto = |days: u32| (stake * days as u128 / total) as u64;
+    upto(day_index + 1) - upto(day_index)
+}
+
 #[program]
 pub mod proven_stake {
     use super::*;
@@ -32,13 +534,31 @@ pub mod proven_stake {
 
     /// Initialize the Escrow Factory (one-time setup)
     /// This creates the root factory account that will create all challenge escrows
-    pub fn initialize_factory(ctx: Context<InitializeFactory>) -> Result<()> {
+    pub fn initialize_factory(
+        ctx: Context<InitializeFactory>,
+        update_delay_seconds: i64,
+    ) -> Result<()> {
+        require!(update_delay_seconds >= 0, ProvenError::InvalidUpdateDelay);
+
         let factory = &mut ctx.accounts.factory;
         factory.authority = ctx.accounts.authority.key();
         factory.treasury = ctx.accounts.treasury.key();
         factory.oracle = ctx.accounts.oracle.key();
         factory.challenge_count = 0;
         factory.day_length_seconds = DEFAULT_DAY_LENGTH_SECONDS;
+        factory.dispute_window_seconds = DEFAULT_DISPUTE_WINDOW_SECONDS;
+        factory.claim_window_seconds = DEFAULT_CLAIM_WINDOW_SECONDS;
+        factory.oracle_committee = Vec::new();
+        factory.oracle_threshold = 1;
+        factory.pending_authority = None;
+        factory.pending_treasury = None;
+        factory.pending_oracle = None;
+        factory.update_delay_seconds = update_delay_seconds;
+        factory.pending_update = None;
+        factory.min_threshold_bps = DEFAULT_MIN_THRESHOLD_BPS;
+        factory.max_threshold_bps = DEFAULT_MAX_THRESHOLD_BPS;
+        factory.fee_bps = 0;
+        factory.max_creator_fee_bps = DEFAULT_MAX_CREATOR_FEE_BPS;
         factory.bump = ctx.bumps.factory;
 
         emit!(FactoryInitialized {
@@ -50,28 +570,134 @@ pub mod proven_stake {
         Ok(())
     }
 
-    /// Update factory settings (authority only)
+    /// Queue a factory settings update (authority only)
+    /// Takes effect through `execute_pending_update` once `update_delay_seconds`
+    /// have passed. Authority, treasury and oracle are rotated with
+    /// `propose_role_transfer` / `accept_role_transfer` instead, so a mistyped key
+    /// can't take over a role.
+    #[allow(clippy::too_many_arguments)]
     pub fn update_factory(
         ctx: Context<UpdateFactory>,
-        new_authority: Option<Pubkey>,
-        new_treasury: Option<Pubkey>,
-        new_oracle: Option<Pubkey>,
         new_day_length_seconds: Option<i64>,
+        new_dispute_window_seconds: Option<i64>,
+        new_update_delay_seconds: Option<i64>,
+        new_min_threshold_bps: Option<u16>,
+        new_max_threshold_bps: Option<u16>,
+        new_fee_bps: Option<u16>,
+        new_max_creator_fee_bps: Option<u16>,
+        new_claim_window_seconds: Option<i64>,
     ) -> Result<()> {
+        if let Some(day_length_seconds) = new_day_length_seconds {
+            require!(day_length_seconds > 0, ProvenError::InvalidDayLength);
+        }
+        if let Some(dispute_window_seconds) = new_dispute_window_seconds {
+            require!(
+                dispute_window_seconds >= 0,
+                ProvenError::InvalidDisputeWindow
+            );
+        }
+        if let Some(update_delay_seconds) = new_update_delay_seconds {
+            require!(update_delay_seconds >= 0, ProvenError::InvalidUpdateDelay);
+        }
+        if let Some(claim_window_seconds) = new_claim_window_seconds {
+            require!(claim_window_seconds > 0, ProvenError::InvalidClaimWindow);
+        }
+        for bps in [new_min_threshold_bps, new_max_threshold_bps].into_iter().flatten() {
+            require!(
+                bps > 0 && bps <= 10000,
+                ProvenError::InvalidThresholdBounds
+            );
+        }
+        // Checked against the current bounds for whichever side isn't changing
+        let factory = &ctx.accounts.factory;
+        require!(
+            new_min_threshold_bps.unwrap_or(factory.min_threshold_bps)
+                <= new_max_threshold_bps.unwrap_or(factory.max_threshold_bps),
+            ProvenError::InvalidThresholdBounds
+        );
+        if let Some(fee_bps) = new_fee_bps {
+            require!(fee_bps <= MAX_FEE_BPS, ProvenError::FeeTooHigh);
+        }
+        if let Some(max_creator_fee_bps) = new_max_creator_fee_bps {
+            require!(
+                max_creator_fee_bps <= MAX_CREATOR_FEE_BPS,
+                ProvenError::FeeTooHigh
+            );
+        }
+
+        queue_factory_update(
+            &mut ctx.accounts.factory,
+            PendingFactoryUpdate {
+                day_length_seconds: new_day_length_seconds,
+                dispute_window_seconds: new_dispute_window_seconds,
+                claim_window_seconds: new_claim_window_seconds,
+                update_delay_seconds: new_update_delay_seconds,
+                min_threshold_bps: new_min_threshold_bps,
+                max_threshold_bps: new_max_threshold_bps,
+                fee_bps: new_fee_bps,
+                max_creator_fee_bps: new_max_creator_fee_bps,
+                oracle_committee: None,
+                oracle_threshold: None,
+                eta: 0,
+            },
+        )
+    }
+
+    /// Apply the queued factory update once its `eta` has passed (authority only)
+    pub fn execute_pending_update(ctx: Context<UpdateFactory>) -> Result<()> {
         let factory = &mut ctx.accounts.factory;
+        let clock = Clock::get()?;
+
+        let update = factory
+            .pending_update
+            .take()
+            .ok_or(ProvenError::NoPendingUpdate)?;
+        require!(
+            clock.unix_timestamp >= update.eta,
+            ProvenError::UpdateNotReady
+        );
 
-        if let Some(authority) = new_authority {
-            factory.authority = authority;
+        if let Some(day_length_seconds) = update.day_length_seconds {
+            factory.day_length_seconds = day_length_seconds;
         }
-        if let Some(treasury) = new_treasury {
-            factory.treasury = treasury;
+        if let Some(dispute_window_seconds) = update.dispute_window_seconds {
+            factory.dispute_window_seconds = dispute_window_seconds;
         }
-        if let Some(oracle) = new_oracle {
-            factory.oracle = oracle;
+        if let Some(claim_window_seconds) = update.claim_window_seconds {
+            factory.claim_window_seconds = claim_window_seconds;
         }
-        if let Some(day_length_seconds) = new_day_length_seconds {
-            require!(day_length_seconds > 0, ProvenError::InvalidDayLength);
-            factory.day_length_seconds = day_length_seconds;
+        if let Some(update_delay_seconds) = update.update_delay_seconds {
+            factory.update_delay_seconds = update_delay_seconds;
+        }
+        if let Some(min_threshold_bps) = update.min_threshold_bps {
+            factory.min_threshold_bps = min_threshold_bps;
+        }
+        if let Some(max_threshold_bps) = update.max_threshold_bps {
+            factory.max_threshold_bps = max_threshold_bps;
+        }
+        require!(
+            factory.min_threshold_bps <= factory.max_threshold_bps,
+            ProvenError::InvalidThresholdBounds
+        );
+        if let Some(fee_bps) = update.fee_bps {
+            factory.fee_bps = fee_bps;
+        }
+        if let Some(max_creator_fee_bps) = update.max_creator_fee_bps {
+            factory.max_creator_fee_bps = max_creator_fee_bps;
+        }
+        if let (Some(members), Some(threshold)) =
+            (update.oracle_committee, update.oracle_threshold)
+        {
+            // The oracle may have rotated since the update was queued
+            validate_oracle_committee(&factory.oracle, &members, threshold)?;
+            factory.oracle_committee = members;
+            factory.oracle_threshold = threshold;
+
+            emit!(OracleCommitteeUpdated {
+                oracle: factory.oracle,
+                members: factory.oracle_committee.clone(),
+                threshold,
+            });
         }
 
         emit!(FactoryUpdated {
@@ -83,29 +709,202 @@ pub mod proven_stake {
         Ok(())
     }
 
+    /// Drop the queued factory update (authority only)
+    pub fn cancel_pending_update(ctx: Context<UpdateFactory>) -> Result<()> {
+        let factory = &mut ctx.accounts.factory;
+
+        let update = factory
+            .pending_update
+            .take()
+            .ok_or(ProvenError::NoPendingUpdate)?;
+
+        emit!(FactoryUpdateCancelled { eta: update.eta });
+
+        Ok(())
+    }
+
+    /// Propose a new holder for a factory role (authority only)
+    /// Nothing changes until the proposed key signs `accept_role_transfer`, which
+    /// is allowed once `update_delay_seconds` have passed.
+    /// Proposing again replaces the pending key and restarts the delay.
+    pub fn propose_role_transfer(
+        ctx: Context<UpdateFactory>,
+        role: FactoryRole,
+        new_holder: Pubkey,
+    ) -> Result<()> {
+        let factory = &mut ctx.accounts.factory;
+
+        require!(
+            new_holder != Pubkey::default() && new_holder != factory.role_holder(role),
+            ProvenError::InvalidRoleTransfer
+        );
+        if role == FactoryRole::Oracle {
+            require!(
+                !factory.oracle_committee.contains(&new_holder),
+                ProvenError::InvalidOracleCommittee
+            );
+        }
+
+        let eta = Clock::get()?
+            .unix_timestamp
+            .checked_add(factory.update_delay_seconds)
+            .ok_or(ProvenError::MathOverflow)?;
+        *factory.pending_role_holder(role) = Some(PendingRole {
+            holder: new_holder,
+            eta,
+        });
+
+        emit!(RoleTransferProposed {
+            role,
+            current: factory.role_holder(role),
+            proposed: new_holder,
+            eta,
+        });
+
+        Ok(())
+    }
+
+    /// Accept a pending role transfer (proposed key must sign)
+    pub fn accept_role_transfer(ctx: Context<AcceptRoleTransfer>, role: FactoryRole) -> Result<()> {
+        let factory = &mut ctx.accounts.factory;
+        let new_holder = ctx.accounts.new_holder.key();
+        let clock = Clock::get()?;
+
+        let pending = factory
+            .pending_role_holder(role)
+            .filter(|pending| pending.holder == new_holder)
+            .ok_or(ProvenError::NoPendingRoleTransfer)?;
+        require!(
+            clock.unix_timestamp >= pending.eta,
+            ProvenError::UpdateNotReady
+        );
+        if role == FactoryRole::Oracle {
+            require!(
+                !factory.oracle_committee.contains(&new_holder),
+                ProvenError::InvalidOracleCommittee
+            );
+        }
+
+        let previous = factory.role_holder(role);
+        match role {
+            FactoryRole::Authority => factory.authority = new_holder,
+            FactoryRole::Treasury => factory.treasury = new_holder,
+            FactoryRole::Oracle => factory.oracle = new_holder,
+        }
+        *factory.pending_role_holder(role) = None;
+
+        emit!(RoleTransferAccepted {
+            role,
+            previous,
+            new_holder,
+        });
+
+        Ok(())
+    }
+
+    /// Cancel a pending role transfer (authority only)
+    pub fn cancel_role_transfer(ctx: Context<UpdateFactory>, role: FactoryRole) -> Result<()> {
+        let factory = &mut ctx.accounts.factory;
+
+        let pending = factory
+            .pending_role_holder(role)
+            .take()
+            .ok_or(ProvenError::NoPendingRoleTransfer)?;
+
+        emit!(RoleTransferCancelled {
+            role,
+            proposed: pending.holder,
+        });
+
+        Ok(())
+    }
+
+    /// Permanently give up factory authority (authority only)
+    /// Factory settings, roles and the oracle committee are frozen afterwards.
+    pub fn renounce_authority(ctx: Context<UpdateFactory>) -> Result<()> {
+        let factory = &mut ctx.accounts.factory;
+
+        let previous = factory.authority;
+        factory.authority = Pubkey::default();
+        factory.pending_authority = None;
+        factory.pending_treasury = None;
+        factory.pending_oracle = None;
+        factory.pending_update = None;
+
+        emit!(AuthorityRenounced { previous });
+
+        Ok(())
+    }
+
+    /// Queue a new oracle committee and approval threshold (authority only)
+    /// The primary `oracle` is always a member; `members` are the additional oracles.
+    /// Once executed, proof recording, revocation, dispute resolution and settlement
+    /// need `threshold` distinct member signatures in the same transaction.
+    pub fn set_oracle_committee(
+        ctx: Context<UpdateFactory>,
+        members: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        validate_oracle_committee(&ctx.accounts.factory.oracle, &members, threshold)?;
+
+        queue_factory_update(
+            &mut ctx.accounts.factory,
+            PendingFactoryUpdate {
+                day_length_seconds: None,
+                dispute_window_seconds: None,
+                claim_window_seconds: None,
+                update_delay_seconds: None,
+                min_threshold_bps: None,
+                max_threshold_bps: None,
+                fee_bps: None,
+                max_creator_fee_bps: None,
+                oracle_committee: Some(members),
+                oracle_threshold: Some(threshold),
+                eta: 0,
+            },
+        )
+    }
+
     // ============================================================
     // CHALLENGE ESCROW INSTRUCTIONS
     // ============================================================
 
     /// Create a new challenge escrow via the factory
     /// Each challenge has its own isolated escrow account
+    /// Payout mode, threshold, fees and participation rules come in `params`.
     pub fn create_challenge(
         ctx: Context<CreateChallenge>,
         challenge_id: String,
         stake_amount: u64,
         total_days: u32,
         start_ts: i64,
+        params: CreateChallengeParams,
     ) -> Result<()> {
+        let CreateChallengeParams {
+            payout_mode,
+            threshold_bps,
+            creator_fee_bps,
+            reward_brackets,
+            early_exit_penalty_bps,
+            late_join_days,
+            max_participants,
+            min_participants,
+        } = params;
+
         // Validations
         require!(stake_amount > 0, ProvenError::InvalidAmount);
-        require!(total_days > 0, ProvenError::InvalidDuration);
+        require!(
+            total_days > 0 && total_days <= MAX_TOTAL_DAYS,
+            ProvenError::InvalidDuration
+        );
         require!(
             start_ts > Clock::get()?.unix_timestamp,
             ProvenError::InvalidStartTime
         );
         require!(!challenge_id.is_empty(), ProvenError::ChallengeIdEmpty);
+        // `str::len` is the byte length, which is what the PDA seed limit applies to
         require!(
-            challenge_id.as_bytes().len() <= ChallengeEscrow::MAX_ID_LENGTH,
+            challenge_id.len() <= ChallengeEscrow::MAX_ID_LENGTH,
             ProvenError::ChallengeIdTooLong
         );
 
@@ -116,6 +915,34 @@ pub mod proven_stake {
             factory.day_length_seconds > 0,
             ProvenError::InvalidDayLength
         );
+        require!(
+            threshold_bps >= factory.min_threshold_bps
+                && threshold_bps <= factory.max_threshold_bps,
+            ProvenError::InvalidThreshold
+        );
+        require!(
+            creator_fee_bps <= factory.max_creator_fee_bps,
+            ProvenError::FeeTooHigh
+        );
+        require!(
+            reward_brackets.is_empty() || payout_mode == PayoutMode::Threshold,
+            ProvenError::InvalidPayoutMode
+        );
+        validate_reward_brackets(&reward_brackets, threshold_bps)?;
+        require!(
+            early_exit_penalty_bps <= MAX_EARLY_EXIT_PENALTY_BPS,
+            ProvenError::PenaltyTooHigh
+        );
+        require!(late_join_days < total_days, ProvenError::InvalidLateJoinWindow);
+        // Daily settlement splits each day's pool over every participant's full stake
+        require!(
+            late_join_days == 0 || payout_mode != PayoutMode::Daily,
+            ProvenError::InvalidPayoutMode
+        );
+        require!(
+            max_participants == 0 || min_participants <= max_participants,
+            ProvenError::InvalidParticipantLimits
+        );
 
         // Initialize challenge escrow
         challenge.challenge_id = challenge_id.clone();
@@ -125,19 +952,54 @@ pub mod proven_stake {
         challenge.escrow_vault = ctx.accounts.escrow_vault.key();
         challenge.stake_amount = stake_amount;
         challenge.total_days = total_days;
-        challenge.threshold_bps = WIN_THRESHOLD_BPS; // Fixed 80%
+        challenge.threshold_bps = threshold_bps;
         challenge.status = ChallengeStatus::Created;
         challenge.start_ts = start_ts;
         challenge.end_ts = start_ts + (total_days as i64 * factory.day_length_seconds);
+        challenge.paused_at = 0;
+        challenge.resume_status = ChallengeStatus::Created;
+        challenge.day_length_seconds = factory.day_length_seconds;
+        challenge.dispute_window_seconds = factory.dispute_window_seconds;
+        challenge.claim_window_seconds = factory.claim_window_seconds;
+        challenge.claim_deadline = 0;
+        challenge.open_disputes = 0;
+        challenge.payout_mode = payout_mode;
         challenge.participant_count = 0;
         challenge.active_participants = 0;
+        challenge.max_participants = max_participants;
+        challenge.min_participants = min_participants;
         challenge.winner_count = 0;
         challenge.loser_count = 0;
         challenge.bonus_per_winner = 0;
         challenge.forfeited_amount = 0;
         challenge.remainder = 0;
         challenge.payouts_claimed_count = 0;
-        challenge.remainder_claimed = 0;
+        challenge.days_settled = 0;
+        challenge.daily_released = 0;
+        challenge.daily_claimed = 0;
+        challenge.daily_proof_counts = vec![0; total_days as usize];
+        // Who judges and who collects forfeits is fixed for the challenge's lifetime
+        challenge.oracle = factory.oracle;
+        challenge.oracle_committee = factory.oracle_committee.clone();
+        challenge.oracle_threshold = factory.oracle_threshold;
+        challenge.treasury = factory.treasury;
+        challenge.fee_bps = factory.fee_bps;
+        challenge.fee_amount = 0;
+        challenge.creator_fee_bps = creator_fee_bps;
+        challenge.creator_fee_amount = 0;
+        challenge.sponsor_pool = 0;
+        challenge.early_exit_penalty_bps = early_exit_penalty_bps;
+        challenge.penalty_pool = 0;
+        challenge.late_join_days = late_join_days;
+        challenge.total_stakes = 0;
+        challenge.losers_stakes = 0;
+        challenge.winner_stakes = 0;
+        challenge.total_proof_days = 0;
+        challenge.unearned_stakes = 0;
+        challenge.bonus_pool = 0;
+        challenge.bonus_claimed = 0;
+        challenge.bracket_payouts = vec![BracketPayout::default(); reward_brackets.len()];
+        challenge.reward_brackets = reward_brackets;
         challenge.bump = ctx.bumps.challenge;
 
         // Increment factory challenge count
@@ -153,14 +1015,30 @@ pub mod proven_stake {
             stake_amount,
             total_days,
             start_ts,
-            threshold_bps: WIN_THRESHOLD_BPS,
+            threshold_bps,
+            required_days: required_days(total_days, threshold_bps),
+            payout_mode: challenge.payout_mode,
+            day_length_seconds: challenge.day_length_seconds,
+            dispute_window_seconds: challenge.dispute_window_seconds,
+            oracle: challenge.oracle,
+            oracle_committee: challenge.oracle_committee.clone(),
+            oracle_threshold: challenge.oracle_threshold,
+            treasury: challenge.treasury,
+            fee_bps: challenge.fee_bps,
+            creator_fee_bps,
+            reward_brackets: challenge.reward_brackets.clone(),
+            early_exit_penalty_bps,
+            late_join_days,
+            max_participants,
+            min_participants,
         });
 
         Ok(())
     }
 
     /// User joins a challenge by staking USDC
-    /// Must join BEFORE the challenge starts (no late joins)
+    /// Must join before the challenge starts, or within its first `late_join_days`
+    /// days for a stake prorated to the days left
     pub fn join_challenge(ctx: Context<JoinChallenge>, challenge_id: String) -> Result<()> {
         let challenge = &mut ctx.accounts.challenge;
         let participant = &mut ctx.accounts.participant;
@@ -171,14 +1049,36 @@ pub mod proven_stake {
             ProvenError::ChallengeIdMismatch
         );
         require!(
-            challenge.status == ChallengeStatus::Created,
+            challenge.status != ChallengeStatus::Paused,
+            ProvenError::ChallengePaused
+        );
+        require!(
+            challenge.status == ChallengeStatus::Created
+                || challenge.status == ChallengeStatus::Started,
             ProvenError::InvalidChallengeStatus
         );
-        // No late joins - must join before start
         require!(
-            clock.unix_timestamp < challenge.start_ts,
+            challenge.max_participants == 0
+                || challenge.participant_count < challenge.max_participants,
+            ProvenError::ChallengeFull
+        );
+
+        let joined_day = if clock.unix_timestamp < challenge.start_ts {
+            0
+        } else {
+            challenge.day_index_at(clock.unix_timestamp)
+        };
+        // Late joins only during the join window
+        require!(
+            clock.unix_timestamp < challenge.start_ts || joined_day < challenge.late_join_days,
             ProvenError::ChallengeStarted
         );
+        let stake = weighted_share(
+            challenge.stake_amount,
+            (challenge.total_days - joined_day) as u64,
+            challenge.total_days as u64,
+        );
+        require!(stake > 0, ProvenError::InvalidAmount);
 
         // Transfer USDC from user to escrow vault
         let cpi_accounts = Transfer {
@@ -188,18 +1088,30 @@ pub mod proven_stake {
         };
         let cpi_program = ctx.accounts.token_program.to_account_info();
         let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-        token::transfer(cpi_ctx, challenge.stake_amount)?;
+        token::transfer(cpi_ctx, stake)?;
 
         // Initialize participant account
         participant.user = ctx.accounts.user.key();
         participant.challenge = challenge.key();
         participant.joined = true;
-        participant.stake_deposited = challenge.stake_amount;
+        participant.stake_deposited = stake;
+        participant.joined_day = joined_day;
         participant.proof_days = 0;
         participant.is_winner = false;
         participant.is_settled = false;
+        participant.bracket = 0;
+        participant.pool_offset = 0;
+        // Until settlement, a participant is owed their stake back (on cancellation)
+        participant.payout_amount = stake;
+        participant.payout_assigned = false;
         participant.payout_claimed = false;
         participant.refund_claimed = false;
+        participant.swept = false;
+        participant.forfeited = false;
+        participant.proof_bitmap = vec![0; Participant::bitmap_len(challenge.total_days)];
+        participant.daily_claim_cursor = 0;
+        participant.open_disputes = 0;
+        participant.revocation_count = 0;
         participant.bump = ctx.bumps.participant;
 
         // Update challenge stats
@@ -208,23 +1120,29 @@ pub mod proven_stake {
             .active_participants
             .checked_add(1)
             .ok_or(ProvenError::MathOverflow)?;
+        challenge.total_stakes = challenge
+            .total_stakes
+            .checked_add(stake)
+            .ok_or(ProvenError::MathOverflow)?;
 
         emit!(ChallengeJoined {
             challenge_id: challenge.key(),
             user: ctx.accounts.user.key(),
-            stake_amount: challenge.stake_amount,
+            stake_amount: stake,
             participant_count: challenge.participant_count,
+            joined_day,
         });
 
         Ok(())
     }
 
-    /// Oracle records a proof submission for a participant
-    /// Called after off-chain verification approves the daily proof
-    pub fn record_proof(ctx: Context<RecordProof>, challenge_id: String) -> Result<()> {
-        let factory = &ctx.accounts.factory;
-        let challenge = &mut ctx.accounts.challenge;
-        let participant = &mut ctx.accounts.participant;
+    /// User leaves a challenge before it starts
+    /// Refunds the stake minus the challenge's early-exit penalty and closes the
+    /// participant account. Penalties go to the winners' bonus pool, or to the
+    /// treasury if nobody wins, the challenge is cancelled, or it pays out daily.
+    pub fn leave_challenge(ctx: Context<LeaveChallenge>, challenge_id: String) -> Result<()> {
+        let challenge = &ctx.accounts.challenge;
+        let participant = &ctx.accounts.participant;
         let clock = Clock::get()?;
 
         require!(
@@ -232,205 +1150,352 @@ pub mod proven_stake {
             ProvenError::ChallengeIdMismatch
         );
         require!(
-            challenge.status == ChallengeStatus::Created
-                || challenge.status == ChallengeStatus::Started,
+            challenge.status == ChallengeStatus::Created,
             ProvenError::InvalidChallengeStatus
         );
         require!(
-            clock.unix_timestamp >= challenge.start_ts,
-            ProvenError::ChallengeNotStarted
-        );
-        require!(
-            clock.unix_timestamp <= challenge.end_ts,
-            ProvenError::ChallengeEnded
+            clock.unix_timestamp < challenge.start_ts,
+            ProvenError::ChallengeStarted
         );
         require!(participant.joined, ProvenError::NotJoined);
-        // Verify oracle authority
-        require!(
-            ctx.accounts.oracle.key() == factory.oracle,
-            ProvenError::InvalidOracle
-        );
-        // Prevent recording more proofs than total days
-        require!(
-            participant.proof_days < challenge.total_days,
-            ProvenError::MaxProofsReached
-        );
 
-        // Auto-start challenge on first proof
-        if challenge.status == ChallengeStatus::Created {
-            challenge.status = ChallengeStatus::Started;
-        }
+        let penalty = fee_share(participant.stake_deposited, challenge.early_exit_penalty_bps);
+        let refund = participant.stake_deposited - penalty;
+
+        // Prepare PDA signer
+        let challenge_id_str = challenge.challenge_id.clone();
+        let factory_key = challenge.factory;
+        let bump = challenge.bump;
+
+        let seeds = &[
+            b"challenge",
+            challenge_id_str.as_bytes(),
+            factory_key.as_ref(),
+            &[bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        // Transfer stake (minus penalty) back to user
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.escrow_vault.to_account_info(),
+            to: ctx.accounts.user_token_account.to_account_info(),
+            authority: ctx.accounts.challenge.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, refund)?;
 
-        // Increment proof days for participant
-        participant.proof_days += 1;
+        let challenge = &mut ctx.accounts.challenge;
+        challenge.penalty_pool = challenge
+            .penalty_pool
+            .checked_add(penalty)
+            .ok_or(ProvenError::MathOverflow)?;
+        challenge.participant_count = challenge
+            .participant_count
+            .checked_sub(1)
+            .ok_or(ProvenError::MathOverflow)?;
+        challenge.active_participants = challenge
+            .active_participants
+            .checked_sub(1)
+            .ok_or(ProvenError::MathOverflow)?;
+        challenge.total_stakes = challenge
+            .total_stakes
+            .checked_sub(ctx.accounts.participant.stake_deposited)
+            .ok_or(ProvenError::MathOverflow)?;
 
-        emit!(ProofRecorded {
+        emit!(ParticipantLeft {
             challenge_id: challenge.key(),
-            user: participant.user,
-            proof_days: participant.proof_days,
-            total_required: required_days(challenge.total_days, challenge.threshold_bps),
+            user: ctx.accounts.user.key(),
+            refund,
+            penalty,
+            participant_count: challenge.participant_count,
         });
 
         Ok(())
     }
 
-    /// Oracle marks the challenge as ended (after end_ts)
-    pub fn settle_challenge(ctx: Context<SettleChallenge>, challenge_id: String) -> Result<()> {
-        let factory = &ctx.accounts.factory;
+    /// Anyone adds tokens to the challenge's prize pool before settlement
+    /// The pool is split among winners on top of their bonus, or refunded to
+    /// sponsors if the challenge is cancelled or nobody wins. `Daily` mode
+    /// challenges don't accept sponsors.
+    pub fn fund_prize_pool(
+        ctx: Context<FundPrizePool>,
+        challenge_id: String,
+        amount: u64,
+    ) -> Result<()> {
         let challenge = &mut ctx.accounts.challenge;
-        let clock = Clock::get()?;
+        let sponsor_deposit = &mut ctx.accounts.sponsor_deposit;
 
         require!(
             challenge.challenge_id == challenge_id,
             ProvenError::ChallengeIdMismatch
         );
+        require!(amount > 0, ProvenError::InvalidAmount);
         require!(
-            challenge.status == ChallengeStatus::Created
-                || challenge.status == ChallengeStatus::Started,
-            ProvenError::InvalidChallengeStatus
-        );
-        require!(
-            clock.unix_timestamp > challenge.end_ts,
-            ProvenError::ChallengeNotEnded
+            challenge.payout_mode != PayoutMode::Daily,
+            ProvenError::InvalidPayoutMode
         );
         require!(
-            ctx.accounts.oracle.key() == factory.oracle,
-            ProvenError::InvalidOracle
+            challenge.status == ChallengeStatus::Created
+                || challenge.status == ChallengeStatus::Started
+                || challenge.status == ChallengeStatus::Ended,
+            ProvenError::InvalidChallengeStatus
         );
 
-        challenge.status = ChallengeStatus::Ended;
+        // Transfer tokens from sponsor to escrow vault
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.sponsor_token_account.to_account_info(),
+            to: ctx.accounts.escrow_vault.to_account_info(),
+            authority: ctx.accounts.sponsor.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token::transfer(cpi_ctx, amount)?;
+
+        // First deposit initializes the record, later ones top it up
+        sponsor_deposit.challenge = challenge.key();
+        sponsor_deposit.sponsor = ctx.accounts.sponsor.key();
+        sponsor_deposit.amount = sponsor_deposit
+            .amount
+            .checked_add(amount)
+            .ok_or(ProvenError::MathOverflow)?;
+        sponsor_deposit.bump = ctx.bumps.sponsor_deposit;
 
-        let required_days = required_days(challenge.total_days, challenge.threshold_bps);
+        challenge.sponsor_pool = challenge
+            .sponsor_pool
+            .checked_add(amount)
+            .ok_or(ProvenError::MathOverflow)?;
 
-        emit!(ChallengeSettlementStarted {
+        emit!(PrizePoolFunded {
             challenge_id: challenge.key(),
-            required_days,
-            participant_count: challenge.participant_count,
+            sponsor: sponsor_deposit.sponsor,
+            amount,
+            sponsor_pool: challenge.sponsor_pool,
         });
 
         Ok(())
     }
 
-    /// Oracle settles each participant (determines winner/loser)
-    pub fn settle_participant(ctx: Context<SettleParticipant>, challenge_id: String) -> Result<()> {
-        let factory = &ctx.accounts.factory;
-        let challenge = &mut ctx.accounts.challenge;
-        let participant = &mut ctx.accounts.participant;
+    /// Sponsor closes their deposit record once the challenge is over
+    /// The deposit is refunded if the challenge was cancelled or had no winners;
+    /// otherwise it was paid out to winners and only the rent is returned.
+    pub fn close_sponsor_deposit(
+        ctx: Context<CloseSponsorDeposit>,
+        challenge_id: String,
+    ) -> Result<()> {
+        let challenge = &ctx.accounts.challenge;
+        let sponsor_deposit = &ctx.accounts.sponsor_deposit;
 
         require!(
             challenge.challenge_id == challenge_id,
             ProvenError::ChallengeIdMismatch
         );
         require!(
-            challenge.status == ChallengeStatus::Ended,
-            ProvenError::InvalidChallengeStatus
-        );
-        require!(
-            ctx.accounts.oracle.key() == factory.oracle,
-            ProvenError::InvalidOracle
+            challenge.status == ChallengeStatus::Settled
+                || challenge.status == ChallengeStatus::Cancelled,
+            ProvenError::ChallengeStillActive
         );
-        require!(!participant.is_settled, ProvenError::AlreadySettled);
 
-        // Calculate required days (80% threshold)
-        let required_days = required_days(challenge.total_days, challenge.threshold_bps);
-
-        if participant.proof_days >= required_days {
-            // Winner!
-            participant.is_winner = true;
-            challenge.winner_count += 1;
+        // An emptied pool means unrefunded deposits were swept to the treasury
+        let refund = if challenge.sponsor_refundable() && challenge.sponsor_pool > 0 {
+            sponsor_deposit.amount
         } else {
-            // Loser - their stake goes to the pool
-            challenge.loser_count += 1;
-        }
+            0
+        };
 
-        participant.is_settled = true;
+        if refund > 0 {
+            // Prepare PDA signer
+            let challenge_id_str = challenge.challenge_id.clone();
+            let factory_key = challenge.factory;
+            let bump = challenge.bump;
+
+            let seeds = &[
+                b"challenge",
+                challenge_id_str.as_bytes(),
+                factory_key.as_ref(),
+                &[bump],
+            ];
+            let signer = &[&seeds[..]];
+
+            // Transfer deposit back to sponsor
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.escrow_vault.to_account_info(),
+                to: ctx.accounts.sponsor_token_account.to_account_info(),
+                authority: ctx.accounts.challenge.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+            token::transfer(cpi_ctx, refund)?;
+
+            let challenge = &mut ctx.accounts.challenge;
+            challenge.sponsor_pool = challenge
+                .sponsor_pool
+                .checked_sub(refund)
+                .ok_or(ProvenError::MathOverflow)?;
+        }
 
-        emit!(ParticipantSettled {
-            challenge_id: challenge.key(),
-            user: participant.user,
-            is_winner: participant.is_winner,
-            proof_days: participant.proof_days,
-            required_days,
+        emit!(SponsorDepositClosed {
+            challenge_id: ctx.accounts.challenge.key(),
+            sponsor: ctx.accounts.sponsor.key(),
+            refunded: refund,
         });
 
         Ok(())
     }
 
-    /// Oracle finalizes settlement and calculates payouts
-    /// Handles three scenarios:
-    /// 1. No winners → All stakes go to platform treasury
-    /// 2. Everyone wins → Return stakes only (no bonus)
-    /// 3. Mixed → Winners split losers' stakes
-    pub fn finalize_settlement(
-        ctx: Context<FinalizeSettlement>,
+    /// Oracle records a proof submission for a participant
+    /// Called after off-chain verification approves the daily proof.
+    /// `day_index` is the challenge day the proof covers; it may be a past day
+    /// (late approval) but never a future one, and each day counts once.
+    /// `proof_hash` commits to the approved media/metadata and is kept in a
+    /// per-day `ProofRecord` so users can verify what was approved.
+    /// After the dispute window, pass the day's open `Dispute` as a remaining account.
+    pub fn record_proof(
+        ctx: Context<RecordProof>,
         challenge_id: String,
+        day_index: u32,
+        proof_hash: [u8; 32],
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.challenge.challenge_id == challenge_id,
+            ProvenError::ChallengeIdMismatch
+        );
+        // Verify oracle authority against the challenge's snapshot
+        require_oracle_quorum(
+            &ctx.accounts.challenge,
+            &ctx.accounts.oracle,
+            ctx.remaining_accounts,
+        )?;
+
+        let payer = ctx.accounts.oracle.key();
+        let bump = ctx.bumps.proof_record;
+        apply_proof(
+            &mut ctx.accounts.challenge,
+            &mut ctx.accounts.participant,
+            &mut ctx.accounts.proof_record,
+            ctx.remaining_accounts,
+            day_index,
+            proof_hash,
+            payer,
+            bump,
+        )
+    }
+
+    /// Participant submits a proof approval signed off-chain by the oracle(s)
+    /// The transaction must include Ed25519 precompile instructions verifying
+    /// `attestation_message` signatures from `oracle_threshold` distinct oracles.
+    /// The user pays fees and rent for the `ProofRecord`; the result is the same
+    /// as `record_proof`.
+    pub fn submit_attested_proof(
+        ctx: Context<SubmitAttestedProof>,
+        challenge_id: String,
+        day_index: u32,
+        proof_hash: [u8; 32],
+        expires_at: i64,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+
+        require!(
+            ctx.accounts.challenge.challenge_id == challenge_id,
+            ProvenError::ChallengeIdMismatch
+        );
+        require!(
+            clock.unix_timestamp <= expires_at,
+            ProvenError::AttestationExpired
+        );
+
+        let message = attestation_message(
+            &ctx.accounts.challenge.key(),
+            &ctx.accounts.user.key(),
+            day_index,
+            &proof_hash,
+            ctx.accounts.participant.revocation_count,
+            expires_at,
+        );
+        require_oracle_attestations(&ctx.accounts.challenge, &ctx.accounts.instructions, &message)?;
+
+        let payer = ctx.accounts.user.key();
+        let bump = ctx.bumps.proof_record;
+        apply_proof(
+            &mut ctx.accounts.challenge,
+            &mut ctx.accounts.participant,
+            &mut ctx.accounts.proof_record,
+            ctx.remaining_accounts,
+            day_index,
+            proof_hash,
+            payer,
+            bump,
+        )
+    }
+
+    /// Oracle revokes a previously recorded proof (e.g. an approval overturned in a dispute)
+    /// Only allowed until the participant is settled. The day's `ProofRecord` is closed,
+    /// so a corrected proof can be recorded for the same day afterwards.
+    pub fn revoke_proof(
+        ctx: Context<RevokeProof>,
+        challenge_id: String,
+        day_index: u32,
+        reason_code: u8,
     ) -> Result<()> {
-        let factory = &ctx.accounts.factory;
         let challenge = &mut ctx.accounts.challenge;
+        let participant = &mut ctx.accounts.participant;
+        let proof_record = &ctx.accounts.proof_record;
 
         require!(
             challenge.challenge_id == challenge_id,
             ProvenError::ChallengeIdMismatch
         );
         require!(
-            challenge.status == ChallengeStatus::Ended,
+            challenge.status == ChallengeStatus::Started
+                || challenge.status == ChallengeStatus::Ended,
             ProvenError::InvalidChallengeStatus
         );
+        require_oracle_quorum(challenge, &ctx.accounts.oracle, ctx.remaining_accounts)?;
+        require!(!participant.is_settled, ProvenError::AlreadySettled);
         require!(
-            ctx.accounts.oracle.key() == factory.oracle,
-            ProvenError::InvalidOracle
-        );
-        // Ensure all participants are settled
-        require!(
-            challenge.winner_count + challenge.loser_count == challenge.participant_count,
-            ProvenError::SettlementIncomplete
+            participant.has_proof_for(day_index),
+            ProvenError::DayNotProven
         );
-
-        // Calculate losers' total stakes
-        let losers_stakes = challenge.loser_count as u64 * challenge.stake_amount;
-
-        challenge.payouts_claimed_count = 0;
-        challenge.remainder_claimed = 0;
-
-        if challenge.winner_count == 0 {
-            // SCENARIO 1: No winners - all stakes go to platform treasury
-            let total_stakes = challenge.participant_count as u64 * challenge.stake_amount;
-            challenge.forfeited_amount = total_stakes;
-            challenge.bonus_per_winner = 0;
-            challenge.remainder = 0;
-
-            emit!(NoWinnersForfeiture {
-                challenge_id: challenge.key(),
-                forfeited_amount: total_stakes,
-                loser_count: challenge.loser_count,
-            });
-        } else if challenge.loser_count == 0 {
-            // SCENARIO 2: Everyone wins - just return stakes, no bonus
-            challenge.bonus_per_winner = 0;
-            challenge.remainder = 0;
-            challenge.forfeited_amount = 0;
-        } else {
-            // SCENARIO 3: Mixed - winners split losers' stakes
-            challenge.bonus_per_winner = losers_stakes / challenge.winner_count as u64;
-            challenge.remainder = losers_stakes % challenge.winner_count as u64;
-            challenge.forfeited_amount = 0;
+        // Settled days are final in daily mode
+        if challenge.payout_mode == PayoutMode::Daily {
+            require!(
+                day_index >= challenge.days_settled,
+                ProvenError::DayAlreadySettled
+            );
         }
 
-        challenge.status = ChallengeStatus::Settled;
+        participant.set_proof(day_index, false);
+        participant.proof_days = participant
+            .proof_days
+            .checked_sub(1)
+            .ok_or(ProvenError::MathOverflow)?;
+        // Invalidates attestations signed before the revocation
+        participant.revocation_count = participant
+            .revocation_count
+            .checked_add(1)
+            .ok_or(ProvenError::MathOverflow)?;
+        challenge.daily_proof_counts[day_index as usize] -= 1;
 
-        emit!(ChallengeSettled {
+        emit!(ProofRevoked {
             challenge_id: challenge.key(),
-            winner_count: challenge.winner_count,
-            loser_count: challenge.loser_count,
-            bonus_per_winner: challenge.bonus_per_winner,
-            forfeited_amount: challenge.forfeited_amount,
+            user: participant.user,
+            day_index,
+            proof_hash: proof_record.proof_hash,
+            reason_code,
+            proof_days: participant.proof_days,
         });
 
         Ok(())
     }
 
-    /// Winner claims their payout (original stake + bonus from losers)
-    pub fn claim_payout(ctx: Context<ClaimPayout>, challenge_id: String) -> Result<()> {
+    /// Rent payer closes a `ProofRecord` once the proof can no longer change
+    /// Allowed after the participant is settled or the challenge is settled or
+    /// cancelled. The participant account must still exist, so close records first.
+    pub fn close_proof_record(
+        ctx: Context<CloseProofRecord>,
+        challenge_id: String,
+        day_index: u32,
+    ) -> Result<()> {
         let challenge = &ctx.accounts.challenge;
         let participant = &ctx.accounts.participant;
 
@@ -439,154 +1504,71 @@ pub mod proven_stake {
             ProvenError::ChallengeIdMismatch
         );
         require!(
-            challenge.status == ChallengeStatus::Settled,
-            ProvenError::ChallengeNotSettled
-        );
-        require!(participant.is_settled, ProvenError::NotSettled);
-        require!(participant.is_winner, ProvenError::NotWinner);
-        require!(
-            !participant.payout_claimed,
-            ProvenError::PayoutAlreadyClaimed
+            participant.is_settled
+                || challenge.status == ChallengeStatus::Settled
+                || challenge.status == ChallengeStatus::Cancelled,
+            ProvenError::ChallengeStillActive
         );
-        require!(
-            challenge.payouts_claimed_count < challenge.winner_count,
-            ProvenError::AllPayoutsClaimed
-        );
-
-        // Calculate total payout (original stake + bonus)
-        let mut bonus = challenge.bonus_per_winner;
-        let mut remainder_increment: u64 = 0;
-
-        // Distribute remainder (dust) to early claimers
-        if challenge.remainder_claimed < challenge.remainder {
-            bonus = bonus.checked_add(1).ok_or(ProvenError::MathOverflow)?;
-            remainder_increment = 1;
-        }
-
-        let payout_amount = challenge
-            .stake_amount
-            .checked_add(bonus)
-            .ok_or(ProvenError::MathOverflow)?;
-
-        // Store values for PDA signer and event
-        let challenge_id_str = challenge.challenge_id.clone();
-        let factory_key = challenge.factory;
-        let bump = challenge.bump;
-        let stake_amount = challenge.stake_amount;
-        let user_pubkey = participant.user;
 
-        let seeds = &[
-            b"challenge",
-            challenge_id_str.as_bytes(),
-            factory_key.as_ref(),
-            &[bump],
-        ];
-        let signer = &[&seeds[..]];
-
-        // Transfer tokens from escrow to winner
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.escrow_vault.to_account_info(),
-            to: ctx.accounts.user_token_account.to_account_info(),
-            authority: ctx.accounts.challenge.to_account_info(),
-        };
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
-        token::transfer(cpi_ctx, payout_amount)?;
-
-        // Update state
-        let challenge = &mut ctx.accounts.challenge;
-        let participant = &mut ctx.accounts.participant;
-
-        participant.payout_claimed = true;
-        challenge.payouts_claimed_count = challenge
-            .payouts_claimed_count
-            .checked_add(1)
-            .ok_or(ProvenError::MathOverflow)?;
-        challenge.remainder_claimed = challenge
-            .remainder_claimed
-            .checked_add(remainder_increment)
-            .ok_or(ProvenError::MathOverflow)?;
-
-        emit!(PayoutClaimed {
+        emit!(ProofRecordClosed {
             challenge_id: challenge.key(),
-            user: user_pubkey,
-            stake_returned: stake_amount,
-            bonus_received: bonus,
-            total_amount: payout_amount,
+            user: participant.user,
+            day_index,
+            payer: ctx.accounts.payer.key(),
         });
 
         Ok(())
     }
 
-    /// Platform treasury claims forfeited stakes (when no winners)
-    pub fn claim_forfeited_stakes(
-        ctx: Context<ClaimForfeitedStakes>,
-        challenge_id: String,
-    ) -> Result<()> {
-        let factory = &ctx.accounts.factory;
-        let challenge = &ctx.accounts.challenge;
+    /// Oracle marks the challenge as ended (after end_ts)
+    pub fn settle_challenge(ctx: Context<SettleChallenge>, challenge_id: String) -> Result<()> {
+        let challenge = &mut ctx.accounts.challenge;
+        let clock = Clock::get()?;
 
         require!(
             challenge.challenge_id == challenge_id,
             ProvenError::ChallengeIdMismatch
         );
         require!(
-            challenge.status == ChallengeStatus::Settled,
-            ProvenError::ChallengeNotSettled
+            challenge.status == ChallengeStatus::Created
+                || challenge.status == ChallengeStatus::Started,
+            ProvenError::InvalidChallengeStatus
         );
-        require!(challenge.winner_count == 0, ProvenError::HasWinners);
+        require_oracle_quorum(challenge, &ctx.accounts.oracle, ctx.remaining_accounts)?;
+        // Undersubscribed challenges can only be cancelled
         require!(
-            challenge.forfeited_amount > 0,
-            ProvenError::NoForfeitedStakes
+            !challenge.undersubscribed(),
+            ProvenError::MinParticipantsNotMet
         );
-        // Only treasury can claim
         require!(
-            ctx.accounts.treasury.key() == factory.treasury,
-            ProvenError::Unauthorized
+            clock.unix_timestamp > challenge.end_ts,
+            ProvenError::ChallengeNotEnded
         );
 
-        let forfeited = challenge.forfeited_amount;
-        let treasury_pubkey = factory.treasury;
-
-        // Prepare PDA signer
-        let challenge_id_str = challenge.challenge_id.clone();
-        let factory_key = challenge.factory;
-        let bump = challenge.bump;
-
-        let seeds = &[
-            b"challenge",
-            challenge_id_str.as_bytes(),
-            factory_key.as_ref(),
-            &[bump],
-        ];
-        let signer = &[&seeds[..]];
-
-        // Transfer forfeited stakes to treasury
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.escrow_vault.to_account_info(),
-            to: ctx.accounts.treasury_token_account.to_account_info(),
-            authority: ctx.accounts.challenge.to_account_info(),
-        };
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
-        token::transfer(cpi_ctx, forfeited)?;
+        challenge.status = ChallengeStatus::Ended;
 
-        // Update state after transfer
-        let challenge = &mut ctx.accounts.challenge;
-        challenge.forfeited_amount = 0;
+        let required_days = required_days(challenge.total_days, challenge.threshold_bps);
 
-        emit!(ForfeitedStakesClaimed {
+        emit!(ChallengeSettlementStarted {
             challenge_id: challenge.key(),
-            treasury: treasury_pubkey,
-            amount: forfeited,
+            required_days,
+            participant_count: challenge.participant_count,
         });
 
         Ok(())
     }
 
-    /// Creator cancels a challenge BEFORE it starts
-    pub fn cancel_challenge(ctx: Context<CancelChallenge>, challenge_id: String) -> Result<()> {
+    /// Participant contests a day without a recorded proof (e.g. a rejected submission)
+    /// Must be opened before the dispute window closes. The participant cannot be
+    /// settled until the oracle resolves it.
+    pub fn open_dispute(
+        ctx: Context<OpenDispute>,
+        challenge_id: String,
+        day_index: u32,
+    ) -> Result<()> {
         let challenge = &mut ctx.accounts.challenge;
+        let participant = &mut ctx.accounts.participant;
+        let dispute = &mut ctx.accounts.dispute;
         let clock = Clock::get()?;
 
         require!(
@@ -594,325 +1576,2135 @@ pub mod proven_stake {
             ProvenError::ChallengeIdMismatch
         );
         require!(
-            challenge.creator == ctx.accounts.creator.key(),
-            ProvenError::Unauthorized
+            challenge.status == ChallengeStatus::Created
+                || challenge.status == ChallengeStatus::Started
+                || challenge.status == ChallengeStatus::Ended,
+            ProvenError::InvalidChallengeStatus
         );
         require!(
-            challenge.status == ChallengeStatus::Created,
-            ProvenError::InvalidChallengeStatus
+            clock.unix_timestamp >= challenge.start_ts,
+            ProvenError::ChallengeNotStarted
         );
-        // Can only cancel before start
         require!(
-            clock.unix_timestamp < challenge.start_ts,
-            ProvenError::ChallengeStarted
+            clock.unix_timestamp <= challenge.dispute_end_ts(),
+            ProvenError::DisputeWindowClosed
+        );
+        require!(participant.joined, ProvenError::NotJoined);
+        require!(!participant.is_settled, ProvenError::AlreadySettled);
+        require!(
+            day_index >= participant.joined_day
+                && day_index < challenge.total_days
+                && day_index <= challenge.day_index_at(clock.unix_timestamp),
+            ProvenError::DayOutOfRange
+        );
+        require!(
+            !participant.has_proof_for(day_index),
+            ProvenError::ProofAlreadyRecorded
         );
+        if challenge.payout_mode == PayoutMode::Daily {
+            require!(
+                day_index >= challenge.days_settled,
+                ProvenError::DayAlreadySettled
+            );
+        }
 
-        challenge.status = ChallengeStatus::Cancelled;
+        dispute.challenge = challenge.key();
+        dispute.participant = participant.key();
+        dispute.user = participant.user;
+        dispute.day_index = day_index;
+        dispute.opened_at = clock.unix_timestamp;
+        dispute.bump = ctx.bumps.dispute;
 
-        emit!(ChallengeCancelled {
+        participant.open_disputes = participant
+            .open_disputes
+            .checked_add(1)
+            .ok_or(ProvenError::MathOverflow)?;
+        challenge.open_disputes = challenge
+            .open_disputes
+            .checked_add(1)
+            .ok_or(ProvenError::MathOverflow)?;
+
+        emit!(DisputeOpened {
             challenge_id: challenge.key(),
-            creator: challenge.creator,
-            participant_count: challenge.participant_count,
+            user: participant.user,
+            day_index,
         });
 
         Ok(())
     }
 
-    /// Participant claims refund after challenge is cancelled
-    pub fn claim_refund(ctx: Context<ClaimRefund>, challenge_id: String) -> Result<()> {
-        let challenge = &ctx.accounts.challenge;
+    /// Oracle resolves a dispute and closes it (rent returns to the user)
+    /// To uphold a dispute, record the proof with `record_proof` first, then resolve.
+    /// `upheld` must match whether the day ended up proven.
+    pub fn resolve_dispute(
+        ctx: Context<ResolveDispute>,
+        challenge_id: String,
+        day_index: u32,
+        upheld: bool,
+    ) -> Result<()> {
+        let challenge = &mut ctx.accounts.challenge;
         let participant = &mut ctx.accounts.participant;
 
         require!(
             challenge.challenge_id == challenge_id,
             ProvenError::ChallengeIdMismatch
         );
+        require_oracle_quorum(challenge, &ctx.accounts.oracle, ctx.remaining_accounts)?;
         require!(
-            challenge.status == ChallengeStatus::Cancelled,
-            ProvenError::NotCancelled
+            ctx.accounts.user.key() == participant.user,
+            ProvenError::Unauthorized
+        );
+        require!(
+            upheld == participant.has_proof_for(day_index),
+            ProvenError::DisputeOutcomeMismatch
         );
-        require!(participant.joined, ProvenError::NotJoined);
-        require!(!participant.refund_claimed, ProvenError::AlreadyClaimed);
 
-        // Prepare PDA signer
-        let challenge_id_str = challenge.challenge_id.clone();
-        let factory = challenge.factory;
-        let bump = challenge.bump;
-
-        let seeds = &[
-            b"challenge",
-            challenge_id_str.as_bytes(),
-            factory.as_ref(),
-            &[bump],
-        ];
-        let signer = &[&seeds[..]];
-
-        // Transfer stake back to user
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.escrow_vault.to_account_info(),
-            to: ctx.accounts.user_token_account.to_account_info(),
-            authority: ctx.accounts.challenge.to_account_info(),
-        };
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
-        token::transfer(cpi_ctx, participant.stake_deposited)?;
-
-        participant.refund_claimed = true;
+        participant.open_disputes = participant
+            .open_disputes
+            .checked_sub(1)
+            .ok_or(ProvenError::MathOverflow)?;
+        challenge.open_disputes = challenge
+            .open_disputes
+            .checked_sub(1)
+            .ok_or(ProvenError::MathOverflow)?;
 
-        emit!(RefundClaimed {
-            challenge_id: ctx.accounts.challenge.key(),
+        emit!(DisputeResolved {
+            challenge_id: challenge.key(),
             user: participant.user,
-            amount: participant.stake_deposited,
+            day_index,
+            upheld,
+            proof_days: participant.proof_days,
         });
 
         Ok(())
     }
 
-    /// Close participant account to reclaim rent
-    pub fn close_participant(ctx: Context<CloseParticipant>, challenge_id: String) -> Result<()> {
-        let authority = &ctx.accounts.authority;
+    /// Oracle settles each participant (determines winner/loser)
+    /// In `Proportional` mode anyone with at least one proof day is a winner and
+    /// the stake for missed days is added to the pool shared by proof days.
+    /// Blocked until the dispute window has passed and the participant's disputes are resolved
+    pub fn settle_participant(ctx: Context<SettleParticipant>, challenge_id: String) -> Result<()> {
         let challenge = &mut ctx.accounts.challenge;
-        let participant = &ctx.accounts.participant;
-        let destination = &ctx.accounts.destination;
+        let participant = &mut ctx.accounts.participant;
+        let clock = Clock::get()?;
 
         require!(
             challenge.challenge_id == challenge_id,
             ProvenError::ChallengeIdMismatch
         );
+        // Undersubscribed challenges can only be cancelled
         require!(
-            participant.user == destination.key(),
-            ProvenError::Unauthorized
+            !challenge.undersubscribed(),
+            ProvenError::MinParticipantsNotMet
         );
         require!(
-            authority.key() == participant.user || authority.key() == challenge.creator,
-            ProvenError::Unauthorized
+            challenge.status == ChallengeStatus::Ended,
+            ProvenError::InvalidChallengeStatus
+        );
+        require_oracle_quorum(challenge, &ctx.accounts.oracle, ctx.remaining_accounts)?;
+        require!(
+            challenge.payout_mode != PayoutMode::Daily,
+            ProvenError::InvalidPayoutMode
+        );
+        require!(!participant.is_settled, ProvenError::AlreadySettled);
+        require!(
+            clock.unix_timestamp > challenge.dispute_end_ts(),
+            ProvenError::DisputeWindowOpen
+        );
+        require!(
+            participant.open_disputes == 0,
+            ProvenError::DisputesPending
         );
 
-        match challenge.status {
-            ChallengeStatus::Settled => {
-                require!(participant.is_settled, ProvenError::NotSettled);
-                if participant.is_winner {
-                    require!(participant.payout_claimed, ProvenError::PayoutNotClaimed);
-                }
-            }
-            ChallengeStatus::Cancelled => {
-                require!(participant.refund_claimed, ProvenError::RefundNotClaimed);
+        // Required days over the participant's own window (late joiners have fewer)
+        let window_days = participant.window_days(challenge.total_days);
+        let required_days = required_days(window_days, challenge.threshold_bps);
+
+        if challenge.payout_mode == PayoutMode::Proportional {
+            // Stake for missed days goes to the pool; the slice position follows
+            // the proof days of winners settled before
+            participant.pool_offset = challenge.total_proof_days;
+            let earned = returned_stake(challenge, participant);
+            challenge.unearned_stakes = challenge
+                .unearned_stakes
+                .checked_add(participant.stake_deposited - earned)
+                .ok_or(ProvenError::MathOverflow)?;
+            challenge.total_proof_days = challenge
+                .total_proof_days
+                .checked_add(participant.proof_days as u64)
+                .ok_or(ProvenError::MathOverflow)?;
+            participant.is_winner = participant.proof_days > 0;
+        } else {
+            participant.is_winner = participant.proof_days >= required_days;
+        }
+
+        if participant.is_winner {
+            // Winner! Placed in the highest bracket reached, if any, and given the
+            // next slice of its pool, sized by stake
+            let proof_days = participant.proof_days;
+            let stake = participant.stake_deposited;
+            if let Some(bracket) = challenge
+                .reward_brackets
+                .iter()
+                .rposition(|b| proof_days >= super::required_days(window_days, b.threshold_bps))
+            {
+                let payout = &mut challenge.bracket_payouts[bracket];
+                participant.bracket = bracket as u8;
+                participant.pool_offset = payout.winner_stakes;
+                payout.winner_count += 1;
+                payout.winner_stakes = payout
+                    .winner_stakes
+                    .checked_add(stake)
+                    .ok_or(ProvenError::MathOverflow)?;
+            } else if challenge.payout_mode == PayoutMode::Threshold {
+                participant.pool_offset = challenge.winner_stakes;
             }
-            _ => return err!(ProvenError::ChallengeStillActive),
+            challenge.winner_count += 1;
+            challenge.winner_stakes = challenge
+                .winner_stakes
+                .checked_add(stake)
+                .ok_or(ProvenError::MathOverflow)?;
+        } else {
+            // Loser - their stake goes to the pool
+            challenge.loser_count += 1;
+            challenge.losers_stakes = challenge
+                .losers_stakes
+                .checked_add(participant.stake_deposited)
+                .ok_or(ProvenError::MathOverflow)?;
         }
 
-        challenge.active_participants = challenge
-            .active_participants
-            .checked_sub(1)
-            .ok_or(ProvenError::MathOverflow)?;
+        participant.is_settled = true;
 
-        emit!(ParticipantClosed {
+        emit!(ParticipantSettled {
             challenge_id: challenge.key(),
             user: participant.user,
-            closed_by: authority.key(),
+            is_winner: participant.is_winner,
+            proof_days: participant.proof_days,
+            required_days,
+            bracket: participant.bracket,
         });
 
         Ok(())
     }
 
-    /// Close the escrow vault to reclaim rent (after all payouts/forfeitures claimed)
-    pub fn close_escrow_vault(
-        ctx: Context<CloseEscrowVault>,
-        challenge_id: String,
-    ) -> Result<()> {
-        let challenge = &ctx.accounts.challenge;
+    /// Participant taps out of a running challenge
+    /// Settles them immediately as a loser, forfeiting the whole stake, so their
+    /// participant account can be closed before the challenge settles.
+    pub fn forfeit(ctx: Context<Forfeit>, challenge_id: String) -> Result<()> {
+        let challenge = &mut ctx.accounts.challenge;
+        let participant = &mut ctx.accounts.participant;
+        let clock = Clock::get()?;
 
         require!(
             challenge.challenge_id == challenge_id,
             ProvenError::ChallengeIdMismatch
         );
         require!(
-            challenge.creator == ctx.accounts.creator.key(),
-            ProvenError::Unauthorized
+            challenge.status == ChallengeStatus::Created
+                || challenge.status == ChallengeStatus::Started
+                || challenge.status == ChallengeStatus::Ended,
+            ProvenError::InvalidChallengeStatus
         );
-
-        // Ensure all funds have been distributed
-        match challenge.status {
-            ChallengeStatus::Settled => {
-                if challenge.winner_count > 0 {
-                    require!(
-                        challenge.payouts_claimed_count == challenge.winner_count,
-                        ProvenError::PendingWinnerPayouts
-                    );
-                }
-                if challenge.winner_count == 0 {
-                    require!(
-                        challenge.forfeited_amount == 0,
-                        ProvenError::ForfeitedStakesUnclaimed
-                    );
-                }
-            }
-            ChallengeStatus::Cancelled => {
-                // For cancelled challenges, ensure all refunds are processed
-                // This is checked via active_participants in close_challenge
-            }
-            _ => return err!(ProvenError::ChallengeStillActive),
-        }
-
-        // Verify escrow vault is empty
+        // Before start, `leave_challenge` applies instead
         require!(
-            ctx.accounts.escrow_vault.amount == 0,
-            ProvenError::EscrowNotEmpty
+            clock.unix_timestamp >= challenge.start_ts,
+            ProvenError::ChallengeNotStarted
+        );
+        // Undersubscribed challenges are cancelled and refunded in full instead
+        require!(
+            !challenge.undersubscribed(),
+            ProvenError::MinParticipantsNotMet
+        );
+        require!(
+            challenge.payout_mode != PayoutMode::Daily,
+            ProvenError::InvalidPayoutMode
+        );
+        require!(participant.joined, ProvenError::NotJoined);
+        require!(!participant.is_settled, ProvenError::AlreadySettled);
+        require!(
+            participant.open_disputes == 0,
+            ProvenError::DisputesPending
         );
 
-        // Close the escrow vault - transfer remaining lamports to creator
-        let challenge_id_str = challenge.challenge_id.clone();
-        let factory_key = challenge.factory;
-        let bump = challenge.bump;
-
-        let seeds = &[
-            b"challenge",
-            challenge_id_str.as_bytes(),
-            factory_key.as_ref(),
-            &[bump],
-        ];
-        let signer = &[&seeds[..]];
+        if challenge.payout_mode == PayoutMode::Proportional {
+            // None of the stake is earned back
+            challenge.unearned_stakes = challenge
+                .unearned_stakes
+                .checked_add(participant.stake_deposited)
+                .ok_or(ProvenError::MathOverflow)?;
+        }
+        challenge.losers_stakes = challenge
+            .losers_stakes
+            .checked_add(participant.stake_deposited)
+            .ok_or(ProvenError::MathOverflow)?;
 
-        let cpi_accounts = token::CloseAccount {
-            account: ctx.accounts.escrow_vault.to_account_info(),
-            destination: ctx.accounts.creator.to_account_info(),
-            authority: ctx.accounts.challenge.to_account_info(),
-        };
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
-        token::close_account(cpi_ctx)?;
+        participant.is_winner = false;
+        participant.is_settled = true;
+        participant.forfeited = true;
+        challenge.loser_count += 1;
 
-        emit!(EscrowVaultClosed {
+        emit!(ParticipantForfeited {
             challenge_id: challenge.key(),
-            creator: challenge.creator,
+            user: participant.user,
+            proof_days: participant.proof_days,
         });
 
         Ok(())
     }
 
-    /// Close challenge account to reclaim rent (after all payouts complete)
-    pub fn close_challenge(ctx: Context<CloseChallenge>, challenge_id: String) -> Result<()> {
-        let creator = &ctx.accounts.creator;
-        let challenge = &ctx.accounts.challenge;
+    /// Oracle finalizes settlement and calculates payouts
+    /// Handles three scenarios:
+    /// 1. No winners → All stakes go to platform treasury
+    /// 2. Everyone wins → Return stakes only (no bonus)
+    /// 3. Mixed → Winners split losers' stakes
+    /// In `Proportional` mode the pool is the stake for missed days, and winners
+    /// share it weighted by their proof days instead of equally.
+    pub fn finalize_settlement(
+        ctx: Context<FinalizeSettlement>,
+        challenge_id: String,
+    ) -> Result<()> {
+        let challenge = &mut ctx.accounts.challenge;
 
         require!(
             challenge.challenge_id == challenge_id,
             ProvenError::ChallengeIdMismatch
         );
-        require!(challenge.creator == creator.key(), ProvenError::Unauthorized);
+        require!(
+            challenge.status == ChallengeStatus::Ended,
+            ProvenError::InvalidChallengeStatus
+        );
+        require_oracle_quorum(challenge, &ctx.accounts.oracle, ctx.remaining_accounts)?;
+        require!(
+            challenge.payout_mode != PayoutMode::Daily,
+            ProvenError::InvalidPayoutMode
+        );
+        // Ensure all participants are settled
+        require!(
+            challenge.winner_count + challenge.loser_count == challenge.participant_count,
+            ProvenError::SettlementIncomplete
+        );
 
-        match challenge.status {
-            ChallengeStatus::Settled => {
-                // If there were winners, all must have claimed
-                if challenge.winner_count > 0 {
-                    require!(
-                        challenge.payouts_claimed_count == challenge.winner_count,
-                        ProvenError::PendingWinnerPayouts
-                    );
-                    require!(
-                        challenge.remainder_claimed == challenge.remainder,
-                        ProvenError::PendingRemainderDistribution
-                    );
-                }
-                // If no winners, forfeited stakes must be claimed by treasury
-                if challenge.winner_count == 0 {
-                    require!(
-                        challenge.forfeited_amount == 0,
-                        ProvenError::ForfeitedStakesUnclaimed
-                    );
-                }
+        // Calculate losers' total stakes
+        let losers_stakes = challenge.losers_stakes;
+
+        challenge.payouts_claimed_count = 0;
+        challenge.fee_amount = 0;
+        challenge.creator_fee_amount = 0;
+        challenge.bonus_pool = 0;
+        challenge.bonus_claimed = 0;
+
+        // Sponsor deposits and early-exit penalties, added to the winners' bonus
+        let prize_pool = challenge
+            .sponsor_pool
+            .checked_add(challenge.penalty_pool)
+            .ok_or(ProvenError::MathOverflow)?;
+
+        if challenge.winner_count == 0 {
+            // SCENARIO 1: No winners - all stakes and penalties go to platform treasury,
+            // minus the creator fee (sponsor deposits are refunded)
+            let total_stakes = challenge.total_stakes;
+            challenge.creator_fee_amount = fee_share(total_stakes, challenge.creator_fee_bps);
+            challenge.forfeited_amount = total_stakes - challenge.creator_fee_amount
+                + challenge.penalty_pool;
+            challenge.bonus_per_winner = 0;
+            challenge.remainder = 0;
+
+            emit!(NoWinnersForfeiture {
+                challenge_id: challenge.key(),
+                forfeited_amount: challenge.forfeited_amount,
+                loser_count: challenge.loser_count,
+            });
+        } else if challenge.payout_mode == PayoutMode::Proportional {
+            // Winners share the stake for missed days, after platform and creator fees,
+            // plus the prize pool, weighted by proof days
+            let unearned = challenge.unearned_stakes;
+            challenge.fee_amount = fee_share(unearned, challenge.fee_bps);
+            challenge.creator_fee_amount = fee_share(unearned, challenge.creator_fee_bps);
+            challenge.bonus_pool = (unearned - challenge.fee_amount - challenge.creator_fee_amount)
+                .checked_add(prize_pool)
+                .ok_or(ProvenError::MathOverflow)?;
+            challenge.bonus_per_winner = 0;
+            challenge.remainder = 0;
+            challenge.forfeited_amount = 0;
+        } else if challenge.loser_count == 0 {
+            // SCENARIO 2: Everyone wins - return stakes, bonus only from the prize pool
+            challenge.bonus_pool = prize_pool;
+            challenge.bonus_per_winner = prize_pool / challenge.winner_count as u64;
+            challenge.remainder = prize_pool % challenge.winner_count as u64;
+            challenge.forfeited_amount = 0;
+        } else {
+            // SCENARIO 3: Mixed - winners split losers' stakes after platform and creator
+            // fees, plus the prize pool
+            challenge.fee_amount = fee_share(losers_stakes, challenge.fee_bps);
+            challenge.creator_fee_amount = fee_share(losers_stakes, challenge.creator_fee_bps);
+            let bonus_pool = (losers_stakes - challenge.fee_amount - challenge.creator_fee_amount)
+                .checked_add(prize_pool)
+                .ok_or(ProvenError::MathOverflow)?;
+            challenge.bonus_pool = bonus_pool;
+            challenge.bonus_per_winner = bonus_pool / challenge.winner_count as u64;
+            challenge.remainder = bonus_pool % challenge.winner_count as u64;
+            challenge.forfeited_amount = 0;
+        }
+
+        if !challenge.reward_brackets.is_empty() && challenge.winner_count > 0 {
+            // Split the bonus pool across brackets by weight * winners' stakes, then by
+            // stake within each bracket. Rounding dust from the first split goes to
+            // the highest occupied bracket.
+            let bonus_pool = challenge.bonus_pool;
+            let weights = challenge
+                .reward_brackets
+                .iter()
+                .zip(challenge.bracket_payouts.iter())
+                .map(|(b, p)| (b.weight as u64).checked_mul(p.winner_stakes))
+                .collect::<Option<Vec<u64>>>()
+                .ok_or(ProvenError::MathOverflow)?;
+            let total_weight = weights
+                .iter()
+                .try_fold(0u64, |total, weight| total.checked_add(*weight))
+                .ok_or(ProvenError::MathOverflow)?;
+
+            let mut bracket_pools: Vec<u64> = weights
+                .iter()
+                .map(|weight| weighted_share(bonus_pool, *weight, total_weight))
+                .collect();
+            let dust = bonus_pool - bracket_pools.iter().sum::<u64>();
+            let top = challenge
+                .bracket_payouts
+                .iter()
+                .rposition(|p| p.winner_count > 0);
+            if let Some(top) = top {
+                bracket_pools[top] += dust;
             }
-            ChallengeStatus::Cancelled => {
-                // All refunds must be claimed (active_participants == 0)
+
+            let mut remainder = 0;
+            for (payout, pool) in challenge.bracket_payouts.iter_mut().zip(bracket_pools) {
+                if payout.winner_count == 0 {
+                    payout.bonus_per_winner = 0;
+                    payout.remainder = 0;
+                    continue;
+                }
+                payout.bonus_per_winner = pool / payout.winner_count as u64;
+                payout.remainder = pool % payout.winner_count as u64;
+                remainder += payout.remainder;
             }
-            _ => return err!(ProvenError::ChallengeStillActive),
+            challenge.bonus_per_winner = 0;
+            challenge.remainder = remainder;
         }
 
-        require!(
-            challenge.active_participants == 0,
-            ProvenError::ParticipantsRemaining
-        );
+        challenge.status = ChallengeStatus::Settled;
+        challenge.open_claim_window(Clock::get()?.unix_timestamp)?;
 
-        emit!(ChallengeClosed {
+        emit!(ChallengeSettled {
             challenge_id: challenge.key(),
-            creator: challenge.creator,
+            winner_count: challenge.winner_count,
+            loser_count: challenge.loser_count,
+            bonus_per_winner: challenge.bonus_per_winner,
+            forfeited_amount: challenge.forfeited_amount,
+            fee_amount: challenge.fee_amount,
+            creator_fee_amount: challenge.creator_fee_amount,
+            sponsor_pool: challenge.sponsor_pool,
+            bonus_pool: challenge.bonus_pool,
+            claim_deadline: challenge.claim_deadline,
         });
 
         Ok(())
     }
-}
 
-// ============================================================
-// ACCOUNT CONTEXTS
-// ============================================================
+    /// Anyone records settled participants' payout entitlements, in batches
+    /// Participants are passed as writable remaining accounts. Claims pay exactly
+    /// the stored `payout_amount`, so every payout reads one authoritative number.
+    pub fn assign_payouts<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AssignPayouts<'info>>,
+        challenge_id: String,
+    ) -> Result<()> {
+        let challenge = &ctx.accounts.challenge;
+
+        require!(
+            challenge.challenge_id == challenge_id,
+            ProvenError::ChallengeIdMismatch
+        );
+        require!(
+            challenge.status == ChallengeStatus::Settled,
+            ProvenError::ChallengeNotSettled
+        );
+        require!(
+            challenge.payout_mode != PayoutMode::Daily,
+            ProvenError::InvalidPayoutMode
+        );
+        require!(
+            !ctx.remaining_accounts.is_empty(),
+            ProvenError::NoParticipantsToAssign
+        );
+
+        for info in ctx.remaining_accounts.iter() {
+            let mut participant = Account::<Participant>::try_from(info)?;
+            require!(
+                participant.challenge == challenge.key(),
+                ProvenError::InvalidParticipant
+            );
+            require!(participant.is_settled, ProvenError::NotSettled);
+            require!(
+                !participant.payout_assigned,
+                ProvenError::PayoutAlreadyAssigned
+            );
+
+            participant.payout_amount = payout_entitlement(challenge, &participant)?;
+            participant.payout_assigned = true;
+            participant.exit(&crate::ID)?;
+
+            emit!(PayoutAssigned {
+                challenge_id: challenge.key(),
+                user: participant.user,
+                is_winner: participant.is_winner,
+                payout_amount: participant.payout_amount,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Winner claims their payout (original stake + bonus from losers)
+    pub fn claim_payout(ctx: Context<ClaimPayout>, challenge_id: String) -> Result<()> {
+        require!(
+            ctx.accounts.challenge.challenge_id == challenge_id,
+            ProvenError::ChallengeIdMismatch
+        );
+
+        pay_out_winner(
+            &mut ctx.accounts.challenge,
+            &mut ctx.accounts.participant,
+            &ctx.accounts.escrow_vault,
+            &ctx.accounts.user_token_account,
+            &ctx.accounts.token_program,
+        )
+    }
+
+    /// Anyone sends a settled winner's payout to the winner's token account
+    /// Lets a payout worker pay users who never come back to claim; the winner's
+    /// associated token account is created (paid by the caller) if missing.
+    pub fn distribute_payout(ctx: Context<DistributePayout>, challenge_id: String) -> Result<()> {
+        require!(
+            ctx.accounts.challenge.challenge_id == challenge_id,
+            ProvenError::ChallengeIdMismatch
+        );
+
+        pay_out_winner(
+            &mut ctx.accounts.challenge,
+            &mut ctx.accounts.participant,
+            &ctx.accounts.escrow_vault,
+            &ctx.accounts.user_token_account,
+            &ctx.accounts.token_program,
+        )
+    }
+
+    /// Oracle settles a single day of a `Daily` payout mode challenge
+    /// Each participant who proved the day gets the day's base release plus an equal
+    /// share of the stakes missed that day. Leftover dust (or the whole pool when
+    /// nobody showed up) is forfeited to the treasury. Days must be settled in order.
+    pub fn settle_day(ctx: Context<SettleDay>, challenge_id: String, day_index: u32) -> Result<()> {
+        let challenge = &mut ctx.accounts.challenge;
+        let daily_settlement = &mut ctx.accounts.daily_settlement;
+        let clock = Clock::get()?;
+
+        require!(
+            challenge.challenge_id == challenge_id,
+            ProvenError::ChallengeIdMismatch
+        );
+        require!(
+            challenge.payout_mode == PayoutMode::Daily,
+            ProvenError::InvalidPayoutMode
+        );
+        require!(
+            challenge.status == ChallengeStatus::Created
+                || challenge.status == ChallengeStatus::Started
+                || challenge.status == ChallengeStatus::Ended,
+            ProvenError::InvalidChallengeStatus
+        );
+        require_oracle_quorum(challenge, &ctx.accounts.oracle, ctx.remaining_accounts)?;
+        // Undersubscribed challenges can only be cancelled
+        require!(
+            !challenge.undersubscribed(),
+            ProvenError::MinParticipantsNotMet
+        );
+        require!(
+            day_index < challenge.total_days,
+            ProvenError::DayOutOfRange
+        );
+        require!(
+            day_index == challenge.days_settled,
+            ProvenError::DaySettlementOutOfOrder
+        );
+        require!(
+            clock.unix_timestamp >= challenge.day_end_ts(day_index),
+            ProvenError::DayNotEnded
+        );
+        // Give users the dispute window to contest the day before it becomes final
+        require!(
+            clock.unix_timestamp
+                > challenge.day_end_ts(day_index) + challenge.dispute_window_seconds,
+            ProvenError::DisputeWindowOpen
+        );
+        require!(challenge.open_disputes == 0, ProvenError::DisputesPending);
+
+        let total_active = challenge.participant_count;
+        let showed_up = challenge.daily_proof_counts[day_index as usize];
+        let missed = total_active
+            .checked_sub(showed_up)
+            .ok_or(ProvenError::MathOverflow)?;
+
+        let base_daily_rate =
+            daily_base_amount(challenge.stake_amount, challenge.total_days, day_index);
+        let missed_pool = base_daily_rate
+            .checked_mul(missed as u64)
+            .ok_or(ProvenError::MathOverflow)?;
+
+        let creator_fee_amount = fee_share(missed_pool, challenge.creator_fee_bps);
+        let (fee_amount, bonus_per_person, forfeited_amount) = if showed_up == 0 {
+            // Nobody showed up - the day's pool goes to the treasury, minus the creator fee
+            (0, 0, missed_pool - creator_fee_amount)
+        } else {
+            let fee_amount = fee_share(missed_pool, challenge.fee_bps);
+            let bonus_pool = missed_pool - fee_amount - creator_fee_amount;
+            (
+                fee_amount,
+                bonus_pool / showed_up as u64,
+                bonus_pool % showed_up as u64,
+            )
+        };
+
+        let total_distributed = base_daily_rate
+            .checked_add(bonus_per_person)
+            .and_then(|per_person| per_person.checked_mul(showed_up as u64))
+            .ok_or(ProvenError::MathOverflow)?;
+
+        daily_settlement.challenge = challenge.key();
+        daily_settlement.day_index = day_index;
+        daily_settlement.total_active = total_active;
+        daily_settlement.showed_up = showed_up;
+        daily_settlement.missed = missed;
+        daily_settlement.base_daily_rate = base_daily_rate;
+        daily_settlement.bonus_per_person = bonus_per_person;
+        daily_settlement.total_distributed = total_distributed;
+        daily_settlement.forfeited_amount = forfeited_amount;
+        daily_settlement.fee_amount = fee_amount;
+        daily_settlement.creator_fee_amount = creator_fee_amount;
+        daily_settlement.settled_at = clock.unix_timestamp;
+        daily_settlement.payer = ctx.accounts.oracle.key();
+        daily_settlement.bump = ctx.bumps.daily_settlement;
+
+        challenge.daily_released = challenge
+            .daily_released
+            .checked_add(total_distributed)
+            .ok_or(ProvenError::MathOverflow)?;
+        challenge.forfeited_amount = challenge
+            .forfeited_amount
+            .checked_add(forfeited_amount)
+            .ok_or(ProvenError::MathOverflow)?;
+        challenge.fee_amount = challenge
+            .fee_amount
+            .checked_add(fee_amount)
+            .ok_or(ProvenError::MathOverflow)?;
+        challenge.creator_fee_amount = challenge
+            .creator_fee_amount
+            .checked_add(creator_fee_amount)
+            .ok_or(ProvenError::MathOverflow)?;
+        challenge.days_settled += 1;
+
+        // Last day settled - the whole stake has been allocated
+        if challenge.days_settled == challenge.total_days {
+            challenge.status = ChallengeStatus::Settled;
+            challenge.open_claim_window(clock.unix_timestamp)?;
+            // Daily mode has no end-of-challenge bonus, so penalties are forfeited
+            challenge.forfeited_amount = challenge
+                .forfeited_amount
+                .checked_add(challenge.penalty_pool)
+                .ok_or(ProvenError::MathOverflow)?;
+        }
+
+        emit!(DaySettled {
+            challenge_id: challenge.key(),
+            day_index,
+            showed_up,
+            missed,
+            base_daily_rate,
+            bonus_per_person,
+            forfeited_amount,
+            fee_amount,
+            creator_fee_amount,
+        });
+
+        Ok(())
+    }
+
+    /// Participant claims everything accrued from settled days in `Daily` payout mode
+    /// Pass the `DailySettlement` accounts as remaining accounts, in day order,
+    /// starting from the participant's `daily_claim_cursor`.
+    pub fn claim_daily_payout<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimDailyPayout<'info>>,
+        challenge_id: String,
+    ) -> Result<()> {
+        let challenge = &ctx.accounts.challenge;
+        let participant = &ctx.accounts.participant;
+
+        require!(
+            challenge.challenge_id == challenge_id,
+            ProvenError::ChallengeIdMismatch
+        );
+        require!(
+            challenge.payout_mode == PayoutMode::Daily,
+            ProvenError::InvalidPayoutMode
+        );
+        require!(participant.joined, ProvenError::NotJoined);
+        require!(
+            !ctx.remaining_accounts.is_empty(),
+            ProvenError::NoDaysToClaim
+        );
+
+        let from_day = participant.daily_claim_cursor;
+        let mut next_day = from_day;
+        let mut amount: u64 = 0;
+
+        for info in ctx.remaining_accounts.iter() {
+            let daily_settlement = Account::<DailySettlement>::try_from(info)?;
+            require!(
+                daily_settlement.challenge == challenge.key()
+                    && daily_settlement.day_index == next_day,
+                ProvenError::InvalidDailySettlement
+            );
+
+            if participant.has_proof_for(next_day) {
+                amount = daily_settlement
+                    .base_daily_rate
+                    .checked_add(daily_settlement.bonus_per_person)
+                    .and_then(|day_amount| amount.checked_add(day_amount))
+                    .ok_or(ProvenError::MathOverflow)?;
+            }
+            next_day += 1;
+        }
+
+        let user_pubkey = participant.user;
+
+        if amount > 0 {
+            let challenge_id_str = challenge.challenge_id.clone();
+            let factory_key = challenge.factory;
+            let bump = challenge.bump;
+
+            let seeds = &[
+                b"challenge",
+                challenge_id_str.as_bytes(),
+                factory_key.as_ref(),
+                &[bump],
+            ];
+            let signer = &[&seeds[..]];
+
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.escrow_vault.to_account_info(),
+                to: ctx.accounts.user_token_account.to_account_info(),
+                authority: ctx.accounts.challenge.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+            token::transfer(cpi_ctx, amount)?;
+        }
+
+        // Update state
+        let challenge = &mut ctx.accounts.challenge;
+        let participant = &mut ctx.accounts.participant;
+
+        participant.daily_claim_cursor = next_day;
+        challenge.daily_claimed = challenge
+            .daily_claimed
+            .checked_add(amount)
+            .ok_or(ProvenError::MathOverflow)?;
+        // Accruals swept to the treasury are no longer claimable
+        require!(
+            challenge.daily_claimed <= challenge.daily_released,
+            ProvenError::PayoutsSwept
+        );
+
+        emit!(DailyPayoutClaimed {
+            challenge_id: challenge.key(),
+            user: user_pubkey,
+            from_day,
+            to_day: next_day,
+            amount,
+        });
+
+        Ok(())
+    }
+
+    /// Rent payer closes a `DailySettlement` once nothing is left to claim against it
+    /// Allowed after the last day is settled and every released accrual has been
+    /// claimed or swept. The challenge account must still exist, so close these first.
+    pub fn close_daily_settlement(
+        ctx: Context<CloseDailySettlement>,
+        challenge_id: String,
+        day_index: u32,
+    ) -> Result<()> {
+        let challenge = &ctx.accounts.challenge;
+
+        require!(
+            challenge.challenge_id == challenge_id,
+            ProvenError::ChallengeIdMismatch
+        );
+        require!(
+            challenge.status == ChallengeStatus::Settled
+                && challenge.days_settled == challenge.total_days,
+            ProvenError::ChallengeNotSettled
+        );
+        // Claims read every day from the cursor on, so all of them must be paid out
+        require!(
+            challenge.daily_claimed == challenge.daily_released,
+            ProvenError::PendingWinnerPayouts
+        );
+
+        emit!(DailySettlementClosed {
+            challenge_id: challenge.key(),
+            day_index,
+            payer: ctx.accounts.payer.key(),
+        });
+
+        Ok(())
+    }
+
+    /// Platform treasury claims forfeited stakes (when no winners, or missed-day dust in `Daily` mode)
+    pub fn claim_forfeited_stakes(
+        ctx: Context<ClaimForfeitedStakes>,
+        challenge_id: String,
+    ) -> Result<()> {
+        let challenge = &ctx.accounts.challenge;
+
+        require!(
+            challenge.challenge_id == challenge_id,
+            ProvenError::ChallengeIdMismatch
+        );
+        // Cancelled challenges forfeit early-exit penalties
+        require!(
+            challenge.status == ChallengeStatus::Settled
+                || challenge.status == ChallengeStatus::Cancelled,
+            ProvenError::ChallengeNotSettled
+        );
+        // Daily mode forfeits missed-day dust alongside payouts, so winners don't apply
+        require!(
+            challenge.payout_mode == PayoutMode::Daily || challenge.winner_count == 0,
+            ProvenError::HasWinners
+        );
+        require!(
+            challenge.forfeited_amount > 0,
+            ProvenError::NoForfeitedStakes
+        );
+        // Only treasury can claim
+        require!(
+            ctx.accounts.treasury.key() == challenge.treasury,
+            ProvenError::Unauthorized
+        );
+
+        let forfeited = challenge.forfeited_amount;
+        let treasury_pubkey = challenge.treasury;
+
+        // Prepare PDA signer
+        let challenge_id_str = challenge.challenge_id.clone();
+        let factory_key = challenge.factory;
+        let bump = challenge.bump;
+
+        let seeds = &[
+            b"challenge",
+            challenge_id_str.as_bytes(),
+            factory_key.as_ref(),
+            &[bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        // Transfer forfeited stakes to treasury
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.escrow_vault.to_account_info(),
+            to: ctx.accounts.treasury_token_account.to_account_info(),
+            authority: ctx.accounts.challenge.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, forfeited)?;
+
+        // Update state after transfer
+        let challenge = &mut ctx.accounts.challenge;
+        challenge.forfeited_amount = 0;
+
+        emit!(ForfeitedStakesClaimed {
+            challenge_id: challenge.key(),
+            treasury: treasury_pubkey,
+            amount: forfeited,
+        });
+
+        Ok(())
+    }
+
+    /// Platform treasury claims the fee taken from losers' stakes
+    pub fn claim_platform_fee(
+        ctx: Context<ClaimForfeitedStakes>,
+        challenge_id: String,
+    ) -> Result<()> {
+        let challenge = &ctx.accounts.challenge;
+
+        require!(
+            challenge.challenge_id == challenge_id,
+            ProvenError::ChallengeIdMismatch
+        );
+        require!(
+            challenge.status == ChallengeStatus::Settled,
+            ProvenError::ChallengeNotSettled
+        );
+        require!(challenge.fee_amount > 0, ProvenError::NoPlatformFee);
+        // Only treasury can claim
+        require!(
+            ctx.accounts.treasury.key() == challenge.treasury,
+            ProvenError::Unauthorized
+        );
+
+        let fee_amount = challenge.fee_amount;
+        let treasury_pubkey = challenge.treasury;
+
+        // Prepare PDA signer
+        let challenge_id_str = challenge.challenge_id.clone();
+        let factory_key = challenge.factory;
+        let bump = challenge.bump;
+
+        let seeds = &[
+            b"challenge",
+            challenge_id_str.as_bytes(),
+            factory_key.as_ref(),
+            &[bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        // Transfer fee to treasury
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.escrow_vault.to_account_info(),
+            to: ctx.accounts.treasury_token_account.to_account_info(),
+            authority: ctx.accounts.challenge.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, fee_amount)?;
+
+        // Update state after transfer
+        let challenge = &mut ctx.accounts.challenge;
+        challenge.fee_amount = 0;
+
+        emit!(PlatformFeeClaimed {
+            challenge_id: challenge.key(),
+            treasury: treasury_pubkey,
+            amount: fee_amount,
+        });
+
+        Ok(())
+    }
+
+    /// Creator claims their share of forfeited stakes
+    pub fn claim_creator_fee(ctx: Context<ClaimCreatorFee>, challenge_id: String) -> Result<()> {
+        let challenge = &ctx.accounts.challenge;
+
+        require!(
+            challenge.challenge_id == challenge_id,
+            ProvenError::ChallengeIdMismatch
+        );
+        require!(
+            challenge.creator == ctx.accounts.creator.key(),
+            ProvenError::Unauthorized
+        );
+        require!(
+            challenge.status == ChallengeStatus::Settled,
+            ProvenError::ChallengeNotSettled
+        );
+        require!(
+            challenge.creator_fee_amount > 0,
+            ProvenError::NoCreatorFee
+        );
+
+        let creator_fee_amount = challenge.creator_fee_amount;
+
+        // Prepare PDA signer
+        let challenge_id_str = challenge.challenge_id.clone();
+        let factory_key = challenge.factory;
+        let bump = challenge.bump;
+
+        let seeds = &[
+            b"challenge",
+            challenge_id_str.as_bytes(),
+            factory_key.as_ref(),
+            &[bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        // Transfer fee to creator
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.escrow_vault.to_account_info(),
+            to: ctx.accounts.creator_token_account.to_account_info(),
+            authority: ctx.accounts.challenge.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, creator_fee_amount)?;
+
+        // Update state after transfer
+        let challenge = &mut ctx.accounts.challenge;
+        challenge.creator_fee_amount = 0;
+
+        emit!(CreatorFeeClaimed {
+            challenge_id: challenge.key(),
+            creator: challenge.creator,
+            amount: creator_fee_amount,
+        });
+
+        Ok(())
+    }
+
+    /// Creator cancels a challenge BEFORE it starts
+    pub fn cancel_challenge(ctx: Context<CancelChallenge>, challenge_id: String) -> Result<()> {
+        let challenge = &mut ctx.accounts.challenge;
+        let clock = Clock::get()?;
+
+        require!(
+            challenge.challenge_id == challenge_id,
+            ProvenError::ChallengeIdMismatch
+        );
+        require!(
+            challenge.creator == ctx.accounts.creator.key(),
+            ProvenError::Unauthorized
+        );
+        require!(
+            challenge.status == ChallengeStatus::Created,
+            ProvenError::InvalidChallengeStatus
+        );
+        // Can only cancel before start
+        require!(
+            clock.unix_timestamp < challenge.start_ts,
+            ProvenError::ChallengeStarted
+        );
+
+        challenge.cancel(clock.unix_timestamp)?;
+
+        emit!(ChallengeCancelled {
+            challenge_id: challenge.key(),
+            creator: challenge.creator,
+            participant_count: challenge.participant_count,
+            claim_deadline: challenge.claim_deadline,
+        });
+
+        Ok(())
+    }
+
+    /// Anyone cancels a challenge that reached its start without `min_participants`
+    /// Proofs and settlement are rejected until the minimum is met, so the challenge
+    /// is still `Created`; participants then reclaim their stakes with `claim_refund`.
+    pub fn cancel_undersubscribed(
+        ctx: Context<CancelUndersubscribed>,
+        challenge_id: String,
+    ) -> Result<()> {
+        let challenge = &mut ctx.accounts.challenge;
+        let clock = Clock::get()?;
+
+        require!(
+            challenge.challenge_id == challenge_id,
+            ProvenError::ChallengeIdMismatch
+        );
+        require!(
+            challenge.status == ChallengeStatus::Created,
+            ProvenError::InvalidChallengeStatus
+        );
+        require!(
+            clock.unix_timestamp >= challenge.start_ts,
+            ProvenError::ChallengeNotStarted
+        );
+        require!(challenge.undersubscribed(), ProvenError::MinParticipantsMet);
+        // Nothing may have been released or forfeited by daily settlement
+        require!(challenge.days_settled == 0, ProvenError::InvalidChallengeStatus);
+
+        challenge.cancel(clock.unix_timestamp)?;
+
+        emit!(ChallengeCancelled {
+            challenge_id: challenge.key(),
+            creator: challenge.creator,
+            participant_count: challenge.participant_count,
+            claim_deadline: challenge.claim_deadline,
+        });
+
+        Ok(())
+    }
+
+    /// Factory authority or the oracle quorum pauses a challenge that hasn't ended
+    /// (e.g. during an outage). Joins and proofs are blocked until `resume_challenge`.
+    /// Additional committee signers are passed as remaining accounts.
+    pub fn pause_challenge(ctx: Context<PauseChallenge>, challenge_id: String) -> Result<()> {
+        let challenge = &mut ctx.accounts.challenge;
+        let signer = ctx.accounts.signer.key();
+        let clock = Clock::get()?;
+
+        require!(
+            challenge.challenge_id == challenge_id,
+            ProvenError::ChallengeIdMismatch
+        );
+        require!(
+            signer == ctx.accounts.factory.authority || challenge.is_oracle(&signer),
+            ProvenError::Unauthorized
+        );
+        // Oracles act as a committee here too, so one key can't stall the challenge
+        if signer != ctx.accounts.factory.authority {
+            require_oracle_quorum(challenge, &ctx.accounts.signer, ctx.remaining_accounts)?;
+        }
+        require!(
+            challenge.status == ChallengeStatus::Created
+                || challenge.status == ChallengeStatus::Started,
+            ProvenError::InvalidChallengeStatus
+        );
+        require!(
+            clock.unix_timestamp < challenge.end_ts,
+            ProvenError::ChallengeEnded
+        );
+
+        challenge.resume_status = challenge.status.clone();
+        challenge.status = ChallengeStatus::Paused;
+        challenge.paused_at = clock.unix_timestamp;
+
+        emit!(ChallengePaused {
+            challenge_id: challenge.key(),
+            paused_by: signer,
+            paused_at: challenge.paused_at,
+        });
+
+        Ok(())
+    }
+
+    /// Factory authority or the oracle quorum resumes a paused challenge
+    /// The challenge clock stops while paused: `start_ts` and `end_ts` move by the
+    /// time paused after start, so days pick up where they left off.
+    pub fn resume_challenge(ctx: Context<PauseChallenge>, challenge_id: String) -> Result<()> {
+        let challenge = &mut ctx.accounts.challenge;
+        let signer = ctx.accounts.signer.key();
+        let clock = Clock::get()?;
+
+        require!(
+            challenge.challenge_id == challenge_id,
+            ProvenError::ChallengeIdMismatch
+        );
+        require!(
+            signer == ctx.accounts.factory.authority || challenge.is_oracle(&signer),
+            ProvenError::Unauthorized
+        );
+        // Oracles act as a committee here too, so one key can't stall the challenge
+        if signer != ctx.accounts.factory.authority {
+            require_oracle_quorum(challenge, &ctx.accounts.signer, ctx.remaining_accounts)?;
+        }
+        require!(
+            challenge.status == ChallengeStatus::Paused,
+            ProvenError::NotPaused
+        );
+
+        // Time paused before start costs no days
+        let paused_from = challenge.paused_at.max(challenge.start_ts);
+        let paused_duration = (clock.unix_timestamp - paused_from).max(0);
+        challenge.start_ts = challenge
+            .start_ts
+            .checked_add(paused_duration)
+            .ok_or(ProvenError::MathOverflow)?;
+        challenge.end_ts = challenge
+            .end_ts
+            .checked_add(paused_duration)
+            .ok_or(ProvenError::MathOverflow)?;
+        challenge.status = challenge.resume_status.clone();
+        challenge.paused_at = 0;
+
+        emit!(ChallengeResumed {
+            challenge_id: challenge.key(),
+            resumed_by: signer,
+            paused_duration,
+            start_ts: challenge.start_ts,
+            end_ts: challenge.end_ts,
+        });
+
+        Ok(())
+    }
+
+    /// Creator opts a challenge into the factory's current oracle set and treasury
+    /// Challenges otherwise keep the ones snapshotted at creation, e.g. after an
+    /// oracle key rotation. Not allowed once the challenge is settled or cancelled.
+    pub fn migrate_challenge_oracles(
+        ctx: Context<MigrateChallengeOracles>,
+        challenge_id: String,
+    ) -> Result<()> {
+        let factory = &ctx.accounts.factory;
+        let challenge = &mut ctx.accounts.challenge;
+
+        require!(
+            challenge.challenge_id == challenge_id,
+            ProvenError::ChallengeIdMismatch
+        );
+        require!(
+            challenge.creator == ctx.accounts.creator.key(),
+            ProvenError::Unauthorized
+        );
+        require!(
+            challenge.status == ChallengeStatus::Created
+                || challenge.status == ChallengeStatus::Started
+                || challenge.status == ChallengeStatus::Ended
+                || challenge.status == ChallengeStatus::Paused,
+            ProvenError::InvalidChallengeStatus
+        );
+
+        let previous_oracle = challenge.oracle;
+        let previous_treasury = challenge.treasury;
+        challenge.oracle = factory.oracle;
+        challenge.oracle_committee = factory.oracle_committee.clone();
+        challenge.oracle_threshold = factory.oracle_threshold;
+        challenge.treasury = factory.treasury;
+
+        emit!(ChallengeOraclesMigrated {
+            challenge_id: challenge.key(),
+            previous_oracle,
+            previous_treasury,
+            oracle: challenge.oracle,
+            oracle_committee: challenge.oracle_committee.clone(),
+            oracle_threshold: challenge.oracle_threshold,
+            treasury: challenge.treasury,
+        });
+
+        Ok(())
+    }
+
+    /// Participant claims refund after challenge is cancelled
+    pub fn claim_refund(ctx: Context<ClaimRefund>, challenge_id: String) -> Result<()> {
+        let challenge = &ctx.accounts.challenge;
+        let participant = &mut ctx.accounts.participant;
+
+        require!(
+            challenge.challenge_id == challenge_id,
+            ProvenError::ChallengeIdMismatch
+        );
+        require!(
+            challenge.status == ChallengeStatus::Cancelled,
+            ProvenError::NotCancelled
+        );
+        require!(participant.joined, ProvenError::NotJoined);
+        require!(!participant.refund_claimed, ProvenError::AlreadyClaimed);
+
+        // Prepare PDA signer
+        let challenge_id_str = challenge.challenge_id.clone();
+        let factory = challenge.factory;
+        let bump = challenge.bump;
+
+        let seeds = &[
+            b"challenge",
+            challenge_id_str.as_bytes(),
+            factory.as_ref(),
+            &[bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        // Transfer stake back to user
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.escrow_vault.to_account_info(),
+            to: ctx.accounts.user_token_account.to_account_info(),
+            authority: ctx.accounts.challenge.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, participant.payout_amount)?;
+
+        participant.refund_claimed = true;
+
+        emit!(RefundClaimed {
+            challenge_id: ctx.accounts.challenge.key(),
+            user: participant.user,
+            amount: participant.payout_amount,
+        });
+
+        Ok(())
+    }
+
+    /// Treasury sweeps payouts and refunds still unclaimed after the claim deadline
+    /// Participants are passed as writable remaining accounts and marked as swept.
+    /// Unclaimed `Daily` accruals and unrefunded sponsor deposits are swept in bulk,
+    /// so the participant, vault and challenge accounts can be closed.
+    pub fn sweep_unclaimed<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimForfeitedStakes<'info>>,
+        challenge_id: String,
+    ) -> Result<()> {
+        let challenge = &mut ctx.accounts.challenge;
+        let clock = Clock::get()?;
+
+        require!(
+            challenge.challenge_id == challenge_id,
+            ProvenError::ChallengeIdMismatch
+        );
+        require!(
+            ctx.accounts.treasury.key() == challenge.treasury,
+            ProvenError::Unauthorized
+        );
+        require!(
+            challenge.status == ChallengeStatus::Settled
+                || challenge.status == ChallengeStatus::Cancelled,
+            ProvenError::ChallengeStillActive
+        );
+        require!(
+            clock.unix_timestamp > challenge.claim_deadline,
+            ProvenError::ClaimDeadlineNotReached
+        );
+
+        // Daily mode payouts are claimed day by day, not from one recorded entitlement
+        let daily_payouts = challenge.payout_mode == PayoutMode::Daily
+            && challenge.status == ChallengeStatus::Settled;
+
+        let mut total: u64 = 0;
+        for info in ctx.remaining_accounts.iter() {
+            let mut participant = Account::<Participant>::try_from(info)?;
+            require!(
+                participant.challenge == challenge.key(),
+                ProvenError::InvalidParticipant
+            );
+
+            if challenge.status == ChallengeStatus::Cancelled {
+                require!(participant.joined, ProvenError::NotJoined);
+                require!(!participant.refund_claimed, ProvenError::NothingToSweep);
+                participant.refund_claimed = true;
+            } else {
+                require!(!daily_payouts, ProvenError::InvalidPayoutMode);
+                require!(
+                    participant.is_winner && !participant.payout_claimed,
+                    ProvenError::NothingToSweep
+                );
+                require!(participant.payout_assigned, ProvenError::PayoutNotAssigned);
+                mark_payout_paid(challenge, &mut participant)?;
+            }
+
+            let amount = participant.payout_amount;
+            total = total.checked_add(amount).ok_or(ProvenError::MathOverflow)?;
+            participant.swept = true;
+            participant.exit(&crate::ID)?;
+
+            emit!(UnclaimedSwept {
+                challenge_id: challenge.key(),
+                user: participant.user,
+                treasury: challenge.treasury,
+                amount,
+            });
+        }
+
+        let daily_amount = if daily_payouts {
+            challenge
+                .daily_released
+                .checked_sub(challenge.daily_claimed)
+                .ok_or(ProvenError::MathOverflow)?
+        } else {
+            0
+        };
+        let sponsor_amount = if challenge.sponsor_refundable() {
+            challenge.sponsor_pool
+        } else {
+            0
+        };
+        total = total
+            .checked_add(daily_amount)
+            .and_then(|total| total.checked_add(sponsor_amount))
+            .ok_or(ProvenError::MathOverflow)?;
+        require!(total > 0, ProvenError::NothingToSweep);
+
+        if daily_amount > 0 || sponsor_amount > 0 {
+            // Counters now match, which also stops later daily claims and sponsor refunds
+            challenge.daily_claimed = challenge.daily_released;
+            challenge.sponsor_pool -= sponsor_amount;
+
+            emit!(PoolsSwept {
+                challenge_id: challenge.key(),
+                treasury: challenge.treasury,
+                daily_amount,
+                sponsor_amount,
+            });
+        }
+
+        // Prepare PDA signer
+        let challenge_id_str = challenge.challenge_id.clone();
+        let factory_key = challenge.factory;
+        let bump = challenge.bump;
+
+        let seeds = &[
+            b"challenge",
+            challenge_id_str.as_bytes(),
+            factory_key.as_ref(),
+            &[bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        // Transfer swept funds to treasury
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.escrow_vault.to_account_info(),
+            to: ctx.accounts.treasury_token_account.to_account_info(),
+            authority: ctx.accounts.challenge.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, total)?;
+
+        Ok(())
+    }
+
+    /// Close participant account to reclaim rent
+    pub fn close_participant(ctx: Context<CloseParticipant>, challenge_id: String) -> Result<()> {
+        let authority = &ctx.accounts.authority;
+        let challenge = &mut ctx.accounts.challenge;
+        let participant = &ctx.accounts.participant;
+        let destination = &ctx.accounts.destination;
+
+        require!(
+            challenge.challenge_id == challenge_id,
+            ProvenError::ChallengeIdMismatch
+        );
+        require!(
+            participant.user == destination.key(),
+            ProvenError::Unauthorized
+        );
+        require!(
+            authority.key() == participant.user || authority.key() == challenge.creator,
+            ProvenError::Unauthorized
+        );
+
+        match challenge.status {
+            // Forfeited participants are owed nothing and may leave before settlement
+            ChallengeStatus::Created | ChallengeStatus::Started | ChallengeStatus::Ended
+                if participant.forfeited => {}
+            ChallengeStatus::Settled => match challenge.payout_mode {
+                PayoutMode::Threshold | PayoutMode::Proportional => {
+                    require!(participant.is_settled, ProvenError::NotSettled);
+                    if participant.is_winner {
+                        require!(participant.payout_claimed, ProvenError::PayoutNotClaimed);
+                    }
+                }
+                PayoutMode::Daily => {
+                    // Once every released day is claimed or swept, nothing is owed
+                    require!(
+                        participant.daily_claim_cursor == challenge.total_days
+                            || challenge.daily_claimed == challenge.daily_released,
+                        ProvenError::PayoutNotClaimed
+                    );
+                }
+            },
+            ChallengeStatus::Cancelled => {
+                require!(participant.refund_claimed, ProvenError::RefundNotClaimed);
+            }
+            _ => return err!(ProvenError::ChallengeStillActive),
+        }
+
+        challenge.active_participants = challenge
+            .active_participants
+            .checked_sub(1)
+            .ok_or(ProvenError::MathOverflow)?;
+
+        emit!(ParticipantClosed {
+            challenge_id: challenge.key(),
+            user: participant.user,
+            closed_by: authority.key(),
+        });
+
+        Ok(())
+    }
+
+    /// Close the escrow vault to reclaim rent (after all payouts/forfeitures claimed)
+    pub fn close_escrow_vault(
+        ctx: Context<CloseEscrowVault>,
+        challenge_id: String,
+    ) -> Result<()> {
+        let challenge = &ctx.accounts.challenge;
+
+        require!(
+            challenge.challenge_id == challenge_id,
+            ProvenError::ChallengeIdMismatch
+        );
+        require!(
+            challenge.creator == ctx.accounts.creator.key(),
+            ProvenError::Unauthorized
+        );
+
+        // Ensure all funds have been distributed
+        match challenge.status {
+            ChallengeStatus::Settled => {
+                if challenge.winner_count > 0 {
+                    require!(
+                        challenge.payouts_claimed_count == challenge.winner_count,
+                        ProvenError::PendingWinnerPayouts
+                    );
+                    // The whole bonus pool, dust included, has been paid out
+                    require!(
+                        challenge.bonus_claimed == challenge.bonus_pool,
+                        ProvenError::PendingWinnerPayouts
+                    );
+                }
+                require!(
+                    challenge.daily_claimed == challenge.daily_released,
+                    ProvenError::PendingWinnerPayouts
+                );
+                if challenge.winner_count == 0 {
+                    require!(
+                        challenge.forfeited_amount == 0,
+                        ProvenError::ForfeitedStakesUnclaimed
+                    );
+                }
+                require!(challenge.fee_amount == 0, ProvenError::PlatformFeeUnclaimed);
+                require!(
+                    challenge.creator_fee_amount == 0,
+                    ProvenError::CreatorFeeUnclaimed
+                );
+            }
+            ChallengeStatus::Cancelled => {
+                // For cancelled challenges, ensure all refunds are processed
+                // This is checked via active_participants in close_challenge
+                require!(
+                    challenge.forfeited_amount == 0,
+                    ProvenError::ForfeitedStakesUnclaimed
+                );
+            }
+            _ => return err!(ProvenError::ChallengeStillActive),
+        }
+
+        require!(
+            !challenge.sponsor_refundable() || challenge.sponsor_pool == 0,
+            ProvenError::SponsorRefundsPending
+        );
+
+        // Verify escrow vault is empty
+        require!(
+            ctx.accounts.escrow_vault.amount == 0,
+            ProvenError::EscrowNotEmpty
+        );
+
+        // Close the escrow vault - transfer remaining lamports to creator
+        let challenge_id_str = challenge.challenge_id.clone();
+        let factory_key = challenge.factory;
+        let bump = challenge.bump;
+
+        let seeds = &[
+            b"challenge",
+            challenge_id_str.as_bytes(),
+            factory_key.as_ref(),
+            &[bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        let cpi_accounts = token::CloseAccount {
+            account: ctx.accounts.escrow_vault.to_account_info(),
+            destination: ctx.accounts.creator.to_account_info(),
+            authority: ctx.accounts.challenge.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::close_account(cpi_ctx)?;
+
+        emit!(EscrowVaultClosed {
+            challenge_id: challenge.key(),
+            creator: challenge.creator,
+        });
+
+        Ok(())
+    }
+
+    /// Close challenge account to reclaim rent (after all payouts complete)
+    pub fn close_challenge(ctx: Context<CloseChallenge>, challenge_id: String) -> Result<()> {
+        let creator = &ctx.accounts.creator;
+        let challenge = &ctx.accounts.challenge;
+
+        require!(
+            challenge.challenge_id == challenge_id,
+            ProvenError::ChallengeIdMismatch
+        );
+        require!(challenge.creator == creator.key(), ProvenError::Unauthorized);
+
+        match challenge.status {
+            ChallengeStatus::Settled => {
+                // If there were winners, all must have claimed
+                if challenge.winner_count > 0 {
+                    require!(
+                        challenge.payouts_claimed_count == challenge.winner_count,
+                        ProvenError::PendingWinnerPayouts
+                    );
+                    require!(
+                        challenge.bonus_claimed == challenge.bonus_pool,
+                        ProvenError::PendingRemainderDistribution
+                    );
+                }
+                // Daily mode: every released day must have been claimed
+                require!(
+                    challenge.daily_claimed == challenge.daily_released,
+                    ProvenError::PendingWinnerPayouts
+                );
+                // If no winners, forfeited stakes must be claimed by treasury
+                if challenge.winner_count == 0 {
+                    require!(
+                        challenge.forfeited_amount == 0,
+                        ProvenError::ForfeitedStakesUnclaimed
+                    );
+                }
+                require!(challenge.fee_amount == 0, ProvenError::PlatformFeeUnclaimed);
+                require!(
+                    challenge.creator_fee_amount == 0,
+                    ProvenError::CreatorFeeUnclaimed
+                );
+            }
+            ChallengeStatus::Cancelled => {
+                // All refunds must be claimed (active_participants == 0)
+                require!(
+                    challenge.forfeited_amount == 0,
+                    ProvenError::ForfeitedStakesUnclaimed
+                );
+            }
+            _ => return err!(ProvenError::ChallengeStillActive),
+        }
+
+        require!(
+            challenge.active_participants == 0,
+            ProvenError::ParticipantsRemaining
+        );
+        require!(
+            !challenge.sponsor_refundable() || challenge.sponsor_pool == 0,
+            ProvenError::SponsorRefundsPending
+        );
+
+        emit!(ChallengeClosed {
+            challenge_id: challenge.key(),
+            creator: challenge.creator,
+        });
+
+        Ok(())
+    }
+}
+
+// ============================================================
+// ACCOUNT CONTEXTS
+// ============================================================
+
+#[derive(Accounts)]
+pub struct InitializeFactory<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// CHECK: Treasury account to receive forfeited stakes
+    pub treasury: UncheckedAccount<'info>,
+    /// CHECK: Oracle pubkey for proof verification
+    pub oracle: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + EscrowFactory::LEN,
+        seeds = [b"factory"],
+        bump,
+    )]
+    pub factory: Account<'info, EscrowFactory>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateFactory<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"factory"],
+        bump = factory.bump,
+        has_one = authority @ ProvenError::Unauthorized,
+    )]
+    pub factory: Account<'info, EscrowFactory>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptRoleTransfer<'info> {
+    pub new_holder: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"factory"],
+        bump = factory.bump,
+    )]
+    pub factory: Account<'info, EscrowFactory>,
+}
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String, stake_amount: u64, total_days: u32)]
+pub struct CreateChallenge<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"factory"],
+        bump = factory.bump,
+    )]
+    pub factory: Account<'info, EscrowFactory>,
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + ChallengeEscrow::space(total_days),
+        seeds = [b"challenge", challenge_id.as_bytes(), factory.key().as_ref()],
+        bump,
+    )]
+    pub challenge: Account<'info, ChallengeEscrow>,
+    pub token_mint: Account<'info, Mint>,
+    #[account(
+        init,
+        payer = creator,
+        associated_token::mint = token_mint,
+        associated_token::authority = challenge,
+    )]
+    pub escrow_vault: Account<'info, TokenAccount>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String)]
+pub struct JoinChallenge<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        seeds = [b"factory"],
+        bump = factory.bump,
+    )]
+    pub factory: Account<'info, EscrowFactory>,
+    #[account(
+        mut,
+        seeds = [b"challenge", challenge_id.as_bytes(), factory.key().as_ref()],
+        bump = challenge.bump,
+    )]
+    pub challenge: Account<'info, ChallengeEscrow>,
+    #[account(
+        init,
+        payer = user,
+        space = 8 + Participant::space(challenge.total_days),
+        seeds = [b"participant", challenge.key().as_ref(), user.key().as_ref()],
+        bump,
+    )]
+    pub participant: Account<'info, Participant>,
+    #[account(
+        mut,
+        associated_token::mint = challenge.token_mint,
+        associated_token::authority = user,
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        associated_token::mint = challenge.token_mint,
+        associated_token::authority = challenge,
+    )]
+    pub escrow_vault: Account<'info, TokenAccount>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String)]
+pub struct LeaveChallenge<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        seeds = [b"factory"],
+        bump = factory.bump,
+    )]
+    pub factory: Account<'info, EscrowFactory>,
+    #[account(
+        mut,
+        seeds = [b"challenge", challenge_id.as_bytes(), factory.key().as_ref()],
+        bump = challenge.bump,
+    )]
+    pub challenge: Account<'info, ChallengeEscrow>,
+    #[account(
+        mut,
+        close = user,
+        seeds = [b"participant", challenge.key().as_ref(), user.key().as_ref()],
+        bump = participant.bump,
+    )]
+    pub participant: Account<'info, Participant>,
+    #[account(
+        mut,
+        associated_token::mint = challenge.token_mint,
+        associated_token::authority = user,
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        associated_token::mint = challenge.token_mint,
+        associated_token::authority = challenge,
+    )]
+    pub escrow_vault: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String)]
+pub struct FundPrizePool<'info> {
+    #[account(mut)]
+    pub sponsor: Signer<'info>,
+    #[account(
+        seeds = [b"factory"],
+        bump = factory.bump,
+    )]
+    pub factory: Account<'info, EscrowFactory>,
+    #[account(
+        mut,
+        seeds = [b"challenge", challenge_id.as_bytes(), factory.key().as_ref()],
+        bump = challenge.bump,
+    )]
+    pub challenge: Account<'info, ChallengeEscrow>,
+    #[account(
+        init_if_needed,
+        payer = sponsor,
+        space = 8 + SponsorDeposit::LEN,
+        seeds = [b"sponsor", challenge.key().as_ref(), sponsor.key().as_ref()],
+        bump,
+    )]
+    pub sponsor_deposit: Account<'info, SponsorDeposit>,
+    #[account(
+        mut,
+        associated_token::mint = challenge.token_mint,
+        associated_token::authority = sponsor,
+    )]
+    pub sponsor_token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        associated_token::mint = challenge.token_mint,
+        associated_token::authority = challenge,
+    )]
+    pub escrow_vault: Account<'info, TokenAccount>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String)]
+pub struct CloseSponsorDeposit<'info> {
+    #[account(mut)]
+    pub sponsor: Signer<'info>,
+    #[account(
+        seeds = [b"factory"],
+        bump = factory.bump,
+    )]
+    pub factory: Account<'info, EscrowFactory>,
+    #[account(
+        mut,
+        seeds = [b"challenge", challenge_id.as_bytes(), factory.key().as_ref()],
+        bump = challenge.bump,
+    )]
+    pub challenge: Account<'info, ChallengeEscrow>,
+    #[account(
+        mut,
+        close = sponsor,
+        seeds = [b"sponsor", challenge.key().as_ref(), sponsor.key().as_ref()],
+        bump = sponsor_deposit.bump,
+    )]
+    pub sponsor_deposit: Account<'info, SponsorDeposit>,
+    #[account(
+        mut,
+        associated_token::mint = challenge.token_mint,
+        associated_token::authority = sponsor,
+    )]
+    pub sponsor_token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        associated_token::mint = challenge.token_mint,
+        associated_token::authority = challenge,
+    )]
+    pub escrow_vault: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String, day_index: u32)]
+pub struct RecordProof<'info> {
+    #[account(mut)]
+    pub oracle: Signer<'info>,
+    #[account(
+        seeds = [b"factory"],
+        bump = factory.bump,
+    )]
+    pub factory: Account<'info, EscrowFactory>,
+    #[account(
+        mut,
+        seeds = [b"challenge", challenge_id.as_bytes(), factory.key().as_ref()],
+        bump = challenge.bump,
+    )]
+    pub challenge: Account<'info, ChallengeEscrow>,
+    #[account(
+        mut,
+        seeds = [b"participant", challenge.key().as_ref(), participant.user.as_ref()],
+        bump = participant.bump,
+    )]
+    pub participant: Account<'info, Participant>,
+    #[account(
+        init,
+        payer = oracle,
+        space = 8 + ProofRecord::LEN,
+        seeds = [b"proof", participant.key().as_ref(), &day_index.to_le_bytes()],
+        bump,
+    )]
+    pub proof_record: Account<'info, ProofRecord>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String, day_index: u32)]
+pub struct SubmitAttestedProof<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        seeds = [b"factory"],
+        bump = factory.bump,
+    )]
+    pub factory: Account<'info, EscrowFactory>,
+    #[account(
+        mut,
+        seeds = [b"challenge", challenge_id.as_bytes(), factory.key().as_ref()],
+        bump = challenge.bump,
+    )]
+    pub challenge: Account<'info, ChallengeEscrow>,
+    #[account(
+        mut,
+        seeds = [b"participant", challenge.key().as_ref(), user.key().as_ref()],
+        bump = participant.bump,
+    )]
+    pub participant: Account<'info, Participant>,
+    #[account(
+        init,
+        payer = user,
+        space = 8 + ProofRecord::LEN,
+        seeds = [b"proof", participant.key().as_ref(), &day_index.to_le_bytes()],
+        bump,
+    )]
+    pub proof_record: Account<'info, ProofRecord>,
+    /// CHECK: Instructions sysvar, read for Ed25519 precompile instructions
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String, day_index: u32)]
+pub struct RevokeProof<'info> {
+    #[account(mut)]
+    pub oracle: Signer<'info>,
+    #[account(
+        seeds = [b"factory"],
+        bump = factory.bump,
+    )]
+    pub factory: Account<'info, EscrowFactory>,
+    #[account(
+        mut,
+        seeds = [b"challenge", challenge_id.as_bytes(), factory.key().as_ref()],
+        bump = challenge.bump,
+    )]
+    pub challenge: Account<'info, ChallengeEscrow>,
+    #[account(
+        mut,
+        seeds = [b"participant", challenge.key().as_ref(), participant.user.as_ref()],
+        bump = participant.bump,
+    )]
+    pub participant: Account<'info, Participant>,
+    #[account(
+        mut,
+        close = payer,
+        seeds = [b"proof", participant.key().as_ref(), &day_index.to_le_bytes()],
+        bump = proof_record.bump,
+    )]
+    pub proof_record: Account<'info, ProofRecord>,
+    /// CHECK: Rent refund destination, validated against `proof_record.payer`
+    #[account(mut, address = proof_record.payer)]
+    pub payer: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String, day_index: u32)]
+pub struct CloseProofRecord<'info> {
+    #[account(mut, address = proof_record.payer)]
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [b"factory"],
+        bump = factory.bump,
+    )]
+    pub factory: Account<'info, EscrowFactory>,
+    #[account(
+        seeds = [b"challenge", challenge_id.as_bytes(), factory.key().as_ref()],
+        bump = challenge.bump,
+    )]
+    pub challenge: Account<'info, ChallengeEscrow>,
+    #[account(
+        seeds = [b"participant", challenge.key().as_ref(), participant.user.as_ref()],
+        bump = participant.bump,
+    )]
+    pub participant: Account<'info, Participant>,
+    #[account(
+        mut,
+        close = payer,
+        seeds = [b"proof", participant.key().as_ref(), &day_index.to_le_bytes()],
+        bump = proof_record.bump,
+    )]
+    pub proof_record: Account<'info, ProofRecord>,
+}
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String)]
+pub struct SettleChallenge<'info> {
+    pub oracle: Signer<'info>,
+    #[account(
+        seeds = [b"factory"],
+        bump = factory.bump,
+    )]
+    pub factory: Account<'info, EscrowFactory>,
+    #[account(
+        mut,
+        seeds = [b"challenge", challenge_id.as_bytes(), factory.key().as_ref()],
+        bump = challenge.bump,
+    )]
+    pub challenge: Account<'info, ChallengeEscrow>,
+}
 
 #[derive(Accounts)]
-pub struct InitializeFactory<'info> {
+#[instruction(challenge_id: String, day_index: u32)]
+pub struct OpenDispute<'info> {
     #[account(mut)]
-    pub authority: Signer<'info>,
-    /// CHECK: Treasury account to receive forfeited stakes
-    pub treasury: UncheckedAccount<'info>,
-    /// CHECK: Oracle pubkey for proof verification
-    pub oracle: UncheckedAccount<'info>,
+    pub user: Signer<'info>,
     #[account(
-        init,
-        payer = authority,
-        space = 8 + EscrowFactory::LEN,
         seeds = [b"factory"],
-        bump,
+        bump = factory.bump,
     )]
     pub factory: Account<'info, EscrowFactory>,
+    #[account(
+        mut,
+        seeds = [b"challenge", challenge_id.as_bytes(), factory.key().as_ref()],
+        bump = challenge.bump,
+    )]
+    pub challenge: Account<'info, ChallengeEscrow>,
+    #[account(
+        mut,
+        seeds = [b"participant", challenge.key().as_ref(), user.key().as_ref()],
+        bump = participant.bump,
+    )]
+    pub participant: Account<'info, Participant>,
+    #[account(
+        init,
+        payer = user,
+        space = 8 + Dispute::LEN,
+        seeds = [b"dispute", participant.key().as_ref(), &day_index.to_le_bytes()],
+        bump,
+    )]
+    pub dispute: Account<'info, Dispute>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct UpdateFactory<'info> {
-    #[account(mut)]
-    pub authority: Signer<'info>,
+#[instruction(challenge_id: String, day_index: u32)]
+pub struct ResolveDispute<'info> {
+    pub oracle: Signer<'info>,
+    #[account(
+        seeds = [b"factory"],
+        bump = factory.bump,
+    )]
+    pub factory: Account<'info, EscrowFactory>,
+    #[account(
+        mut,
+        seeds = [b"challenge", challenge_id.as_bytes(), factory.key().as_ref()],
+        bump = challenge.bump,
+    )]
+    pub challenge: Account<'info, ChallengeEscrow>,
+    #[account(
+        mut,
+        seeds = [b"participant", challenge.key().as_ref(), participant.user.as_ref()],
+        bump = participant.bump,
+    )]
+    pub participant: Account<'info, Participant>,
     #[account(
         mut,
+        close = user,
+        seeds = [b"dispute", participant.key().as_ref(), &day_index.to_le_bytes()],
+        bump = dispute.bump,
+    )]
+    pub dispute: Account<'info, Dispute>,
+    /// CHECK: validated against participant.user in handler
+    #[account(mut)]
+    pub user: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String)]
+pub struct SettleParticipant<'info> {
+    pub oracle: Signer<'info>,
+    #[account(
         seeds = [b"factory"],
         bump = factory.bump,
-        has_one = authority @ ProvenError::Unauthorized,
     )]
     pub factory: Account<'info, EscrowFactory>,
+    #[account(
+        mut,
+        seeds = [b"challenge", challenge_id.as_bytes(), factory.key().as_ref()],
+        bump = challenge.bump,
+    )]
+    pub challenge: Account<'info, ChallengeEscrow>,
+    #[account(
+        mut,
+        seeds = [b"participant", challenge.key().as_ref(), participant.user.as_ref()],
+        bump = participant.bump,
+    )]
+    pub participant: Account<'info, Participant>,
 }
 
 #[derive(Accounts)]
 #[instruction(challenge_id: String)]
-pub struct CreateChallenge<'info> {
-    #[account(mut)]
-    pub creator: Signer<'info>,
+pub struct Forfeit<'info> {
+    pub user: Signer<'info>,
+    #[account(
+        seeds = [b"factory"],
+        bump = factory.bump,
+    )]
+    pub factory: Account<'info, EscrowFactory>,
+    #[account(
+        mut,
+        seeds = [b"challenge", challenge_id.as_bytes(), factory.key().as_ref()],
+        bump = challenge.bump,
+    )]
+    pub challenge: Account<'info, ChallengeEscrow>,
     #[account(
         mut,
+        seeds = [b"participant", challenge.key().as_ref(), user.key().as_ref()],
+        bump = participant.bump,
+    )]
+    pub participant: Account<'info, Participant>,
+}
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String)]
+pub struct FinalizeSettlement<'info> {
+    pub oracle: Signer<'info>,
+    #[account(
         seeds = [b"factory"],
         bump = factory.bump,
     )]
     pub factory: Account<'info, EscrowFactory>,
     #[account(
-        init,
-        payer = creator,
-        space = 8 + ChallengeEscrow::LEN,
+        mut,
         seeds = [b"challenge", challenge_id.as_bytes(), factory.key().as_ref()],
-        bump,
+        bump = challenge.bump,
     )]
     pub challenge: Account<'info, ChallengeEscrow>,
-    pub token_mint: Account<'info, Mint>,
+}
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String)]
+pub struct AssignPayouts<'info> {
     #[account(
-        init,
-        payer = creator,
-        associated_token::mint = token_mint,
-        associated_token::authority = challenge,
+        seeds = [b"factory"],
+        bump = factory.bump,
     )]
-    pub escrow_vault: Account<'info, TokenAccount>,
-    pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub factory: Account<'info, EscrowFactory>,
+    #[account(
+        seeds = [b"challenge", challenge_id.as_bytes(), factory.key().as_ref()],
+        bump = challenge.bump,
+    )]
+    pub challenge: Account<'info, ChallengeEscrow>,
 }
 
 #[derive(Accounts)]
 #[instruction(challenge_id: String)]
-pub struct JoinChallenge<'info> {
+pub struct ClaimPayout<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
     #[account(
@@ -927,11 +3719,9 @@ pub struct JoinChallenge<'info> {
     )]
     pub challenge: Account<'info, ChallengeEscrow>,
     #[account(
-        init,
-        payer = user,
-        space = 8 + Participant::LEN,
+        mut,
         seeds = [b"participant", challenge.key().as_ref(), user.key().as_ref()],
-        bump,
+        bump = participant.bump,
     )]
     pub participant: Account<'info, Participant>,
     #[account(
@@ -946,14 +3736,14 @@ pub struct JoinChallenge<'info> {
         associated_token::authority = challenge,
     )]
     pub escrow_vault: Account<'info, TokenAccount>,
-    pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
 }
 
 #[derive(Accounts)]
 #[instruction(challenge_id: String)]
-pub struct RecordProof<'info> {
-    pub oracle: Signer<'info>,
+pub struct DistributePayout<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
     #[account(
         seeds = [b"factory"],
         bump = factory.bump,
@@ -967,15 +3757,36 @@ pub struct RecordProof<'info> {
     pub challenge: Account<'info, ChallengeEscrow>,
     #[account(
         mut,
-        seeds = [b"participant", challenge.key().as_ref(), participant.user.as_ref()],
+        seeds = [b"participant", challenge.key().as_ref(), user.key().as_ref()],
         bump = participant.bump,
     )]
     pub participant: Account<'info, Participant>,
+    /// CHECK: Winner's wallet, only used as the token account owner
+    pub user: UncheckedAccount<'info>,
+    #[account(address = challenge.token_mint)]
+    pub token_mint: Account<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = token_mint,
+        associated_token::authority = user,
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        associated_token::mint = challenge.token_mint,
+        associated_token::authority = challenge,
+    )]
+    pub escrow_vault: Account<'info, TokenAccount>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
 }
 
 #[derive(Accounts)]
-#[instruction(challenge_id: String)]
-pub struct SettleChallenge<'info> {
+#[instruction(challenge_id: String, day_index: u32)]
+pub struct SettleDay<'info> {
+    #[account(mut)]
     pub oracle: Signer<'info>,
     #[account(
         seeds = [b"factory"],
@@ -988,12 +3799,22 @@ pub struct SettleChallenge<'info> {
         bump = challenge.bump,
     )]
     pub challenge: Account<'info, ChallengeEscrow>,
+    #[account(
+        init,
+        payer = oracle,
+        space = 8 + DailySettlement::LEN,
+        seeds = [b"daily", challenge.key().as_ref(), &day_index.to_le_bytes()],
+        bump,
+    )]
+    pub daily_settlement: Account<'info, DailySettlement>,
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
 #[instruction(challenge_id: String)]
-pub struct SettleParticipant<'info> {
-    pub oracle: Signer<'info>,
+pub struct ClaimDailyPayout<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
     #[account(
         seeds = [b"factory"],
         bump = factory.bump,
@@ -1007,34 +3828,54 @@ pub struct SettleParticipant<'info> {
     pub challenge: Account<'info, ChallengeEscrow>,
     #[account(
         mut,
-        seeds = [b"participant", challenge.key().as_ref(), participant.user.as_ref()],
+        seeds = [b"participant", challenge.key().as_ref(), user.key().as_ref()],
         bump = participant.bump,
     )]
     pub participant: Account<'info, Participant>,
+    #[account(
+        mut,
+        associated_token::mint = challenge.token_mint,
+        associated_token::authority = user,
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        associated_token::mint = challenge.token_mint,
+        associated_token::authority = challenge,
+    )]
+    pub escrow_vault: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
 }
 
 #[derive(Accounts)]
-#[instruction(challenge_id: String)]
-pub struct FinalizeSettlement<'info> {
-    pub oracle: Signer<'info>,
+#[instruction(challenge_id: String, day_index: u32)]
+pub struct CloseDailySettlement<'info> {
+    #[account(mut, address = daily_settlement.payer)]
+    pub payer: Signer<'info>,
     #[account(
         seeds = [b"factory"],
         bump = factory.bump,
     )]
     pub factory: Account<'info, EscrowFactory>,
     #[account(
-        mut,
         seeds = [b"challenge", challenge_id.as_bytes(), factory.key().as_ref()],
         bump = challenge.bump,
     )]
     pub challenge: Account<'info, ChallengeEscrow>,
+    #[account(
+        mut,
+        close = payer,
+        seeds = [b"daily", challenge.key().as_ref(), &day_index.to_le_bytes()],
+        bump = daily_settlement.bump,
+    )]
+    pub daily_settlement: Account<'info, DailySettlement>,
 }
 
 #[derive(Accounts)]
 #[instruction(challenge_id: String)]
-pub struct ClaimPayout<'info> {
+pub struct ClaimForfeitedStakes<'info> {
     #[account(mut)]
-    pub user: Signer<'info>,
+    pub treasury: Signer<'info>,
     #[account(
         seeds = [b"factory"],
         bump = factory.bump,
@@ -1048,16 +3889,40 @@ pub struct ClaimPayout<'info> {
     pub challenge: Account<'info, ChallengeEscrow>,
     #[account(
         mut,
-        seeds = [b"participant", challenge.key().as_ref(), user.key().as_ref()],
-        bump = participant.bump,
+        associated_token::mint = challenge.token_mint,
+        associated_token::authority = treasury,
     )]
-    pub participant: Account<'info, Participant>,
+    pub treasury_token_account: Account<'info, TokenAccount>,
     #[account(
         mut,
         associated_token::mint = challenge.token_mint,
-        associated_token::authority = user,
+        associated_token::authority = challenge,
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub escrow_vault: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String)]
+pub struct ClaimCreatorFee<'info> {
+    pub creator: Signer<'info>,
+    #[account(
+        seeds = [b"factory"],
+        bump = factory.bump,
+    )]
+    pub factory: Account<'info, EscrowFactory>,
+    #[account(
+        mut,
+        seeds = [b"challenge", challenge_id.as_bytes(), factory.key().as_ref()],
+        bump = challenge.bump,
+    )]
+    pub challenge: Account<'info, ChallengeEscrow>,
+    #[account(
+        mut,
+        associated_token::mint = challenge.token_mint,
+        associated_token::authority = creator,
+    )]
+    pub creator_token_account: Account<'info, TokenAccount>,
     #[account(
         mut,
         associated_token::mint = challenge.token_mint,
@@ -1069,9 +3934,42 @@ pub struct ClaimPayout<'info> {
 
 #[derive(Accounts)]
 #[instruction(challenge_id: String)]
-pub struct ClaimForfeitedStakes<'info> {
+pub struct CancelChallenge<'info> {
     #[account(mut)]
-    pub treasury: Signer<'info>,
+    pub creator: Signer<'info>,
+    #[account(
+        seeds = [b"factory"],
+        bump = factory.bump,
+    )]
+    pub factory: Account<'info, EscrowFactory>,
+    #[account(
+        mut,
+        seeds = [b"challenge", challenge_id.as_bytes(), factory.key().as_ref()],
+        bump = challenge.bump,
+    )]
+    pub challenge: Account<'info, ChallengeEscrow>,
+}
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String)]
+pub struct CancelUndersubscribed<'info> {
+    #[account(
+        seeds = [b"factory"],
+        bump = factory.bump,
+    )]
+    pub factory: Account<'info, EscrowFactory>,
+    #[account(
+        mut,
+        seeds = [b"challenge", challenge_id.as_bytes(), factory.key().as_ref()],
+        bump = challenge.bump,
+    )]
+    pub challenge: Account<'info, ChallengeEscrow>,
+}
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String)]
+pub struct PauseChallenge<'info> {
+    pub signer: Signer<'info>,
     #[account(
         seeds = [b"factory"],
         bump = factory.bump,
@@ -1083,25 +3981,11 @@ pub struct ClaimForfeitedStakes<'info> {
         bump = challenge.bump,
     )]
     pub challenge: Account<'info, ChallengeEscrow>,
-    #[account(
-        mut,
-        associated_token::mint = challenge.token_mint,
-        associated_token::authority = treasury,
-    )]
-    pub treasury_token_account: Account<'info, TokenAccount>,
-    #[account(
-        mut,
-        associated_token::mint = challenge.token_mint,
-        associated_token::authority = challenge,
-    )]
-    pub escrow_vault: Account<'info, TokenAccount>,
-    pub token_program: Program<'info, Token>,
 }
 
 #[derive(Accounts)]
 #[instruction(challenge_id: String)]
-pub struct CancelChallenge<'info> {
-    #[account(mut)]
+pub struct MigrateChallengeOracles<'info> {
     pub creator: Signer<'info>,
     #[account(
         seeds = [b"factory"],
@@ -1240,12 +4124,149 @@ pub struct EscrowFactory {
     pub challenge_count: u64,
     /// Day length used to compute challenge end time (seconds)
     pub day_length_seconds: i64,
+    /// Dispute window after a challenge ends (seconds)
+    pub dispute_window_seconds: i64,
+    /// Claim window after a challenge is settled or cancelled (seconds)
+    pub claim_window_seconds: i64,
+    /// Additional oracles that can co-sign alongside `oracle`
+    pub oracle_committee: Vec<Pubkey>,
+    /// Distinct oracle signatures required for proof and settlement decisions
+    pub oracle_threshold: u8,
+    /// Proposed authority awaiting acceptance
+    pub pending_authority: Option<PendingRole>,
+    /// Proposed treasury awaiting acceptance
+    pub pending_treasury: Option<PendingRole>,
+    /// Proposed oracle awaiting acceptance
+    pub pending_oracle: Option<PendingRole>,
+    /// Minimum delay before queued updates and role transfers take effect (seconds)
+    pub update_delay_seconds: i64,
+    /// Queued settings update
+    pub pending_update: Option<PendingFactoryUpdate>,
+    /// Lowest win threshold a challenge may use (basis points)
+    pub min_threshold_bps: u16,
+    /// Highest win threshold a challenge may use (basis points)
+    pub max_threshold_bps: u16,
+    /// Platform fee on losers' stakes (basis points, capped by `MAX_FEE_BPS`)
+    pub fee_bps: u16,
+    /// Highest creator fee a challenge may set (basis points, capped by `MAX_CREATOR_FEE_BPS`)
+    pub max_creator_fee_bps: u16,
     /// PDA bump seed
     pub bump: u8,
 }
 
 impl EscrowFactory {
-    pub const LEN: usize = 32 + 32 + 32 + 8 + 8 + 1; // 113 bytes
+    pub const LEN: usize = 32 + 32 + 32 + 8 + 8 + 8 + 8 // roles, count, timings
+        + (4 + 32 * MAX_ORACLE_COMMITTEE) + 1 // committee
+        + (1 + PendingRole::LEN) * 3 // pending roles
+        + 8 + (1 + PendingFactoryUpdate::LEN) // timelock
+        + 2 + 2 // threshold bounds
+        + 2 + 2 // fee_bps, max_creator_fee_bps
+        + 1; // 657 bytes
+
+    /// Current holder of a role
+    pub fn role_holder(&self, role: FactoryRole) -> Pubkey {
+        match role {
+            FactoryRole::Authority => self.authority,
+            FactoryRole::Treasury => self.treasury,
+            FactoryRole::Oracle => self.oracle,
+        }
+    }
+
+    /// Pending transfer slot for a role
+    pub fn pending_role_holder(&mut self, role: FactoryRole) -> &mut Option<PendingRole> {
+        match role {
+            FactoryRole::Authority => &mut self.pending_authority,
+            FactoryRole::Treasury => &mut self.pending_treasury,
+            FactoryRole::Oracle => &mut self.pending_oracle,
+        }
+    }
+}
+
+/// Challenge rules chosen by the creator in `create_challenge`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CreateChallengeParams {
+    pub payout_mode: PayoutMode,
+    /// Share of days needed to win, within the factory's bounds
+    pub threshold_bps: u16,
+    /// Host's cut of forfeited stakes, up to the factory's cap
+    pub creator_fee_bps: u16,
+    /// Optional completion brackets (`Threshold` mode only)
+    pub reward_brackets: Vec<RewardBracket>,
+    /// Share of the stake withheld from users who leave before start
+    pub early_exit_penalty_bps: u16,
+    /// Days after start during which users may still join at a prorated stake
+    pub late_join_days: u32,
+    /// Join cap (0 for no cap)
+    pub max_participants: u32,
+    /// Below this at start, anyone can cancel with `cancel_undersubscribed`
+    pub min_participants: u32,
+}
+
+/// Completion bracket: winners at or above `threshold_bps` share the bonus
+/// pool in proportion to `weight`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct RewardBracket {
+    /// Minimum completion for the bracket (basis points)
+    pub threshold_bps: u16,
+    /// Relative share of the bonus pool per winner
+    pub weight: u16,
+}
+
+impl RewardBracket {
+    pub const LEN: usize = 2 + 2; // 4 bytes
+}
+
+/// Bracket outcome, computed at settlement
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct BracketPayout {
+    /// Winners placed in the bracket
+    pub winner_count: u32,
+    /// Bonus paid to each winner in the bracket
+    pub bonus_per_winner: u64,
+    /// Dust left after splitting the bracket's pool equally
+    pub remainder: u64,
+    /// Stakes of the bracket's winners
+    pub winner_stakes: u64,
+}
+
+impl BracketPayout {
+    pub const LEN: usize = 4 + 8 + 8 + 8; // 28 bytes
+}
+
+/// Role transfer awaiting acceptance by the proposed key
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct PendingRole {
+    /// Proposed holder
+    pub holder: Pubkey,
+    /// Earliest time the transfer can be accepted
+    pub eta: i64,
+}
+
+impl PendingRole {
+    pub const LEN: usize = 32 + 8; // 40 bytes
+}
+
+/// Factory settings change queued behind the update delay
+/// `None` fields are left unchanged when executed.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PendingFactoryUpdate {
+    pub day_length_seconds: Option<i64>,
+    pub dispute_window_seconds: Option<i64>,
+    pub claim_window_seconds: Option<i64>,
+    pub update_delay_seconds: Option<i64>,
+    pub min_threshold_bps: Option<u16>,
+    pub max_threshold_bps: Option<u16>,
+    pub fee_bps: Option<u16>,
+    pub max_creator_fee_bps: Option<u16>,
+    pub oracle_committee: Option<Vec<Pubkey>>,
+    pub oracle_threshold: Option<u8>,
+    /// Earliest time the update can be executed
+    pub eta: i64,
+}
+
+impl PendingFactoryUpdate {
+    pub const LEN: usize =
+        9 + 9 + 9 + 9 + 3 + 3 + 3 + 3 + (1 + 4 + 32 * MAX_ORACLE_COMMITTEE) + 2 + 8; // 223 bytes
 }
 
 /// Challenge Escrow - Individual escrow for each challenge
@@ -1273,15 +4294,23 @@ pub struct ChallengeEscrow {
     pub start_ts: i64,
     /// Challenge end timestamp
     pub end_ts: i64,
+    /// When the challenge was paused (0 if not paused)
+    pub paused_at: i64,
+    /// Status to restore on `resume_challenge`
+    pub resume_status: ChallengeStatus,
     /// Total participants joined
     pub participant_count: u32,
     /// Active participants (not closed)
     pub active_participants: u32,
+    /// Most participants allowed to join (0 for no cap)
+    pub max_participants: u32,
+    /// Participants needed by start for the challenge to run
+    pub min_participants: u32,
     /// Number of winners
     pub winner_count: u32,
     /// Number of losers
     pub loser_count: u32,
-    /// Bonus per winner (losers' stakes / winners)
+    /// Average bonus per winner (bonus pool / winners); slices are weighted by stake
     pub bonus_per_winner: u64,
     /// Forfeited stakes (when no winners)
     pub forfeited_amount: u64,
@@ -1289,8 +4318,69 @@ pub struct ChallengeEscrow {
     pub remainder: u64,
     /// Number of payouts claimed
     pub payouts_claimed_count: u32,
-    /// Remainder tokens claimed
-    pub remainder_claimed: u64,
+    /// Day length used for this challenge (seconds)
+    pub day_length_seconds: i64,
+    /// How stakes are paid out
+    pub payout_mode: PayoutMode,
+    /// Days settled so far (`Daily` mode)
+    pub days_settled: u32,
+    /// Total credited to participants by `settle_day` (`Daily` mode)
+    pub daily_released: u64,
+    /// Total paid out through `claim_daily_payout` (`Daily` mode)
+    pub daily_claimed: u64,
+    /// Dispute window after `end_ts` (seconds)
+    pub dispute_window_seconds: i64,
+    /// Claim window after settlement or cancellation (seconds)
+    pub claim_window_seconds: i64,
+    /// After this, unclaimed payouts and refunds can be swept (0 until settled or cancelled)
+    pub claim_deadline: i64,
+    /// Disputes awaiting oracle resolution
+    pub open_disputes: u32,
+    /// Primary oracle, snapshotted from the factory at creation
+    pub oracle: Pubkey,
+    /// Additional oracles, snapshotted from the factory at creation
+    pub oracle_committee: Vec<Pubkey>,
+    /// Distinct oracle signatures required, snapshotted from the factory at creation
+    pub oracle_threshold: u8,
+    /// Treasury receiving forfeits, snapshotted from the factory at creation
+    pub treasury: Pubkey,
+    /// Platform fee on losers' stakes, snapshotted from the factory at creation
+    pub fee_bps: u16,
+    /// Platform fee owed to the treasury and not yet claimed
+    pub fee_amount: u64,
+    /// Creator's cut of forfeited stakes (basis points)
+    pub creator_fee_bps: u16,
+    /// Creator fee owed to the creator and not yet claimed
+    pub creator_fee_amount: u64,
+    /// Sponsor contributions to the prize pool still held for winners or refunds
+    pub sponsor_pool: u64,
+    /// Share of the stake withheld when a participant leaves before start (basis points)
+    pub early_exit_penalty_bps: u16,
+    /// Early-exit penalties collected
+    pub penalty_pool: u64,
+    /// Days after start during which users may still join, at a prorated stake
+    pub late_join_days: u32,
+    /// Stakes deposited by current participants
+    pub total_stakes: u64,
+    /// Stakes of participants settled as losers
+    pub losers_stakes: u64,
+    /// Stakes of participants settled as winners
+    pub winner_stakes: u64,
+    /// Proof days across settled participants (`Proportional` mode)
+    pub total_proof_days: u64,
+    /// Stake not earned back by settled participants (`Proportional` mode)
+    pub unearned_stakes: u64,
+    /// Pool shared among winners on top of their returned stake, by stake or by
+    /// proof days in `Proportional` mode
+    pub bonus_pool: u64,
+    /// Paid out of `bonus_pool` so far
+    pub bonus_claimed: u64,
+    /// Completion brackets with bonus weights, lowest first (`Threshold` mode)
+    pub reward_brackets: Vec<RewardBracket>,
+    /// Winners and bonus per bracket, parallel to `reward_brackets`
+    pub bracket_payouts: Vec<BracketPayout>,
+    /// Participants with a recorded proof, per day
+    pub daily_proof_counts: Vec<u32>,
     /// PDA bump seed
     pub bump: u8,
 }
@@ -1309,17 +4399,106 @@ impl ChallengeEscrow {
         + 1   // status
         + 8   // start_ts
         + 8   // end_ts
+        + 8   // paused_at
+        + 1   // resume_status
         + 4   // participant_count
         + 4   // active_participants
+        + 4   // max_participants
+        + 4   // min_participants
         + 4   // winner_count
         + 4   // loser_count
         + 8   // bonus_per_winner
         + 8   // forfeited_amount
         + 8   // remainder
         + 4   // payouts_claimed_count
-        + 8   // remainder_claimed
+        + 8   // day_length_seconds
+        + 1   // payout_mode
+        + 4   // days_settled
+        + 8   // daily_released
+        + 8   // daily_claimed
+        + 8   // dispute_window_seconds
+        + 8   // claim_window_seconds
+        + 8   // claim_deadline
+        + 4   // open_disputes
+        + 32  // oracle
+        + 4 + 32 * MAX_ORACLE_COMMITTEE // oracle_committee
+        + 1   // oracle_threshold
+        + 32  // treasury
+        + 2   // fee_bps
+        + 8   // fee_amount
+        + 2   // creator_fee_bps
+        + 8   // creator_fee_amount
+        + 8   // sponsor_pool
+        + 2   // early_exit_penalty_bps
+        + 8   // penalty_pool
+        + 4   // late_join_days
+        + 8   // total_stakes
+        + 8   // losers_stakes
+        + 8   // winner_stakes
+        + 8   // total_proof_days
+        + 8   // unearned_stakes
+        + 8   // bonus_pool
+        + 8   // bonus_claimed
+        + 4 + RewardBracket::LEN * MAX_REWARD_BRACKETS // reward_brackets
+        + 4 + BracketPayout::LEN * MAX_REWARD_BRACKETS // bracket_payouts
+        + 4   // daily_proof_counts (Vec discriminator, items sized by `space`)
         + 1;  // bump
-              // Total: 226 bytes
+              // Total: 781 bytes + 4 per day
+
+    /// Account size for a challenge running `total_days` days
+    pub fn space(total_days: u32) -> usize {
+        Self::LEN + 4 * total_days as usize
+    }
+
+    /// Whether fewer than `min_participants` have joined
+    pub fn undersubscribed(&self) -> bool {
+        self.participant_count < self.min_participants
+    }
+
+    /// Whether sponsor deposits go back to sponsors rather than to winners
+    pub fn sponsor_refundable(&self) -> bool {
+        self.status == ChallengeStatus::Cancelled
+            || (self.status == ChallengeStatus::Settled && self.winner_count == 0)
+    }
+
+    /// Day index (0-based) that `timestamp` falls in
+    pub fn day_index_at(&self, timestamp: i64) -> u32 {
+        ((timestamp - self.start_ts) / self.day_length_seconds) as u32
+    }
+
+    /// Timestamp at which day `day_index` is over
+    pub fn day_end_ts(&self, day_index: u32) -> i64 {
+        self.start_ts + (day_index as i64 + 1) * self.day_length_seconds
+    }
+
+    /// Timestamp at which the dispute window closes
+    pub fn dispute_end_ts(&self) -> i64 {
+        self.end_ts + self.dispute_window_seconds
+    }
+
+    /// Moves the challenge to `Cancelled`: opens the refund claim window and
+    /// forfeits early-exit penalties to the treasury
+    pub fn cancel(&mut self, now: i64) -> Result<()> {
+        self.status = ChallengeStatus::Cancelled;
+        self.forfeited_amount = self
+            .forfeited_amount
+            .checked_add(self.penalty_pool)
+            .ok_or(ProvenError::MathOverflow)?;
+        self.open_claim_window(now)
+    }
+
+    /// Starts the claim window once the challenge is settled or cancelled
+    pub fn open_claim_window(&mut self, now: i64) -> Result<()> {
+        self.claim_deadline = now
+            .checked_add(self.claim_window_seconds)
+            .ok_or(ProvenError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Whether `key` is the challenge's primary oracle or a committee member
+    pub fn is_oracle(&self, key: &Pubkey) -> bool {
+        *key == self.oracle || self.oracle_committee.contains(key)
+    }
 }
 
 /// Participant in a challenge
@@ -1333,22 +4512,195 @@ pub struct Participant {
     pub joined: bool,
     /// Amount staked
     pub stake_deposited: u64,
+    /// Day the participant joined on; their window runs from it to the end
+    pub joined_day: u32,
     /// Number of verified proof days
     pub proof_days: u32,
     /// Whether user won
     pub is_winner: bool,
     /// Whether settlement determined winner/loser
     pub is_settled: bool,
+    /// Index into the challenge's `reward_brackets` (winners only)
+    pub bracket: u8,
+    /// Start of the winner's slice of the bonus pool, assigned at settlement
+    pub pool_offset: u64,
+    /// Amount owed to the participant: the stake until settlement, then the
+    /// entitlement written by `assign_payouts`
+    pub payout_amount: u64,
+    /// Whether `payout_amount` holds the settled entitlement
+    pub payout_assigned: bool,
     /// Whether payout was claimed
     pub payout_claimed: bool,
     /// Whether refund was claimed (for cancellation)
     pub refund_claimed: bool,
+    /// Whether an unclaimed payout or refund was swept to the treasury
+    pub swept: bool,
+    /// Whether the participant tapped out via `forfeit`
+    pub forfeited: bool,
+    /// Next day to claim in `Daily` payout mode
+    pub daily_claim_cursor: u32,
+    /// Disputes awaiting oracle resolution
+    pub open_disputes: u32,
+    /// Proofs revoked so far; bound into attestation messages
+    pub revocation_count: u32,
+    /// One bit per challenge day, set when a proof was recorded for that day
+    pub proof_bitmap: Vec<u8>,
     /// PDA bump seed
     pub bump: u8,
 }
 
 impl Participant {
-    pub const LEN: usize = 32 + 32 + 1 + 8 + 4 + 1 + 1 + 1 + 1 + 1; // 82 bytes
+    pub const LEN: usize = 32 + 32 + 1 + 8 + 4 + 4 + 1 + 1 + 1 + 1 + 1 + 1 + 1
+        + 8 + 8 + 1 + 4 + 4 + 4 + 4 + 1; // 122 bytes + bitmap
+
+    /// Account size for a participant in a challenge running `total_days` days
+    pub fn space(total_days: u32) -> usize {
+        Self::LEN + Self::bitmap_len(total_days)
+    }
+
+    /// Days the participant takes part in, from `joined_day` to the end
+    pub fn window_days(&self, total_days: u32) -> u32 {
+        total_days - self.joined_day
+    }
+
+    pub fn bitmap_len(total_days: u32) -> usize {
+        total_days.div_ceil(8) as usize
+    }
+
+    pub fn has_proof_for(&self, day_index: u32) -> bool {
+        self.proof_bitmap
+            .get(day_index as usize / 8)
+            .is_some_and(|byte| byte & (1 << (day_index % 8)) != 0)
+    }
+
+    pub fn set_proof(&mut self, day_index: u32, proven: bool) {
+        let byte = &mut self.proof_bitmap[day_index as usize / 8];
+        if proven {
+            *byte |= 1 << (day_index % 8);
+        } else {
+            *byte &= !(1 << (day_index % 8));
+        }
+    }
+}
+
+/// Proof Record - Commitment to the proof approved for one participant-day
+#[account]
+pub struct ProofRecord {
+    /// Challenge account
+    pub challenge: Pubkey,
+    /// Participant account
+    pub participant: Pubkey,
+    /// User's wallet address
+    pub user: Pubkey,
+    /// Day index (0-based) the proof covers
+    pub day_index: u32,
+    /// Hash of the approved proof media/metadata
+    pub proof_hash: [u8; 32],
+    /// Timestamp the proof was recorded
+    pub recorded_at: i64,
+    /// Account that paid rent for this record (refunded on revoke or close)
+    pub payer: Pubkey,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl ProofRecord {
+    pub const LEN: usize = 32 + 32 + 32 + 4 + 32 + 8 + 32 + 1; // 173 bytes
+}
+
+/// Dispute - A participant contesting a day without a recorded proof
+#[account]
+pub struct Dispute {
+    /// Challenge account
+    pub challenge: Pubkey,
+    /// Participant account
+    pub participant: Pubkey,
+    /// User's wallet address
+    pub user: Pubkey,
+    /// Day index (0-based) being contested
+    pub day_index: u32,
+    /// Timestamp the dispute was opened
+    pub opened_at: i64,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl Dispute {
+    pub const LEN: usize = 32 + 32 + 32 + 4 + 8 + 1; // 109 bytes
+}
+
+/// Sponsor Deposit - One sponsor's contribution to a challenge's prize pool
+#[account]
+pub struct SponsorDeposit {
+    /// Challenge account
+    pub challenge: Pubkey,
+    /// Sponsor's wallet address
+    pub sponsor: Pubkey,
+    /// Total tokens deposited by this sponsor
+    pub amount: u64,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl SponsorDeposit {
+    pub const LEN: usize = 32 + 32 + 8 + 1; // 73 bytes
+}
+
+/// Daily Settlement - Outcome of one settled day in `Daily` payout mode
+#[account]
+pub struct DailySettlement {
+    /// Challenge this day belongs to
+    pub challenge: Pubkey,
+    /// Day index (0-based)
+    pub day_index: u32,
+    /// Participants expected to show up
+    pub total_active: u32,
+    /// Participants with a recorded proof for the day
+    pub showed_up: u32,
+    /// Participants without a proof for the day
+    pub missed: u32,
+    /// Stake released to each participant who showed up
+    pub base_daily_rate: u64,
+    /// Share of the missed pool paid to each participant who showed up
+    pub bonus_per_person: u64,
+    /// Total credited to participants for the day
+    pub total_distributed: u64,
+    /// Missed pool left for the treasury (dust, or everything if nobody showed up)
+    pub forfeited_amount: u64,
+    /// Platform fee taken from the missed pool
+    pub fee_amount: u64,
+    /// Creator fee taken from the missed pool
+    pub creator_fee_amount: u64,
+    /// Settlement timestamp
+    pub settled_at: i64,
+    /// Account that paid rent for this record (refunded on close)
+    pub payer: Pubkey,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl DailySettlement {
+    pub const LEN: usize = 32 + 4 + 4 + 4 + 4 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 32 + 1; // 137 bytes
+}
+
+/// Factory roles that can be rotated with a two-step transfer
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum FactoryRole {
+    Authority,
+    Treasury,
+    Oracle,
+}
+
+/// Payout model, chosen at challenge creation
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum PayoutMode {
+    /// End-of-challenge split: winners (>= threshold) share losers' stakes
+    Threshold,
+    /// Stake released day by day; missed days fund a bonus for those who showed up
+    Daily,
+    /// End-of-challenge split: each participant gets back the stake for their proof
+    /// days, and the stake for missed days is shared weighted by proof days
+    Proportional,
 }
 
 /// Challenge status enum
@@ -1364,6 +4716,8 @@ pub enum ChallengeStatus {
     Settled,
     /// Challenge cancelled, refunds available
     Cancelled,
+    /// Challenge paused, joins and proofs blocked until resumed
+    Paused,
 }
 
 // ============================================================
@@ -1384,6 +4738,59 @@ pub struct FactoryUpdated {
     pub oracle: Pubkey,
 }
 
+#[event]
+pub struct FactoryUpdateQueued {
+    pub day_length_seconds: Option<i64>,
+    pub dispute_window_seconds: Option<i64>,
+    pub claim_window_seconds: Option<i64>,
+    pub update_delay_seconds: Option<i64>,
+    pub min_threshold_bps: Option<u16>,
+    pub max_threshold_bps: Option<u16>,
+    pub fee_bps: Option<u16>,
+    pub max_creator_fee_bps: Option<u16>,
+    pub oracle_committee: Option<Vec<Pubkey>>,
+    pub oracle_threshold: Option<u8>,
+    pub eta: i64,
+}
+
+#[event]
+pub struct FactoryUpdateCancelled {
+    pub eta: i64,
+}
+
+#[event]
+pub struct RoleTransferProposed {
+    pub role: FactoryRole,
+    pub current: Pubkey,
+    pub proposed: Pubkey,
+    pub eta: i64,
+}
+
+#[event]
+pub struct RoleTransferAccepted {
+    pub role: FactoryRole,
+    pub previous: Pubkey,
+    pub new_holder: Pubkey,
+}
+
+#[event]
+pub struct RoleTransferCancelled {
+    pub role: FactoryRole,
+    pub proposed: Pubkey,
+}
+
+#[event]
+pub struct AuthorityRenounced {
+    pub previous: Pubkey,
+}
+
+#[event]
+pub struct OracleCommitteeUpdated {
+    pub oracle: Pubkey,
+    pub members: Vec<Pubkey>,
+    pub threshold: u8,
+}
+
 #[event]
 pub struct ChallengeCreated {
     pub challenge_id: Pubkey,
@@ -1393,6 +4800,32 @@ pub struct ChallengeCreated {
     pub total_days: u32,
     pub start_ts: i64,
     pub threshold_bps: u16,
+    pub required_days: u32,
+    pub payout_mode: PayoutMode,
+    pub day_length_seconds: i64,
+    pub dispute_window_seconds: i64,
+    pub oracle: Pubkey,
+    pub oracle_committee: Vec<Pubkey>,
+    pub oracle_threshold: u8,
+    pub treasury: Pubkey,
+    pub fee_bps: u16,
+    pub creator_fee_bps: u16,
+    pub reward_brackets: Vec<RewardBracket>,
+    pub early_exit_penalty_bps: u16,
+    pub late_join_days: u32,
+    pub max_participants: u32,
+    pub min_participants: u32,
+}
+
+#[event]
+pub struct ChallengeOraclesMigrated {
+    pub challenge_id: Pubkey,
+    pub previous_oracle: Pubkey,
+    pub previous_treasury: Pubkey,
+    pub oracle: Pubkey,
+    pub oracle_committee: Vec<Pubkey>,
+    pub oracle_threshold: u8,
+    pub treasury: Pubkey,
 }
 
 #[event]
@@ -1401,16 +4834,53 @@ pub struct ChallengeJoined {
     pub user: Pubkey,
     pub stake_amount: u64,
     pub participant_count: u32,
+    pub joined_day: u32,
 }
 
 #[event]
 pub struct ProofRecorded {
     pub challenge_id: Pubkey,
     pub user: Pubkey,
+    pub day_index: u32,
+    pub proof_hash: [u8; 32],
     pub proof_days: u32,
     pub total_required: u32,
 }
 
+#[event]
+pub struct ProofRevoked {
+    pub challenge_id: Pubkey,
+    pub user: Pubkey,
+    pub day_index: u32,
+    pub proof_hash: [u8; 32],
+    pub reason_code: u8,
+    pub proof_days: u32,
+}
+
+#[event]
+pub struct ProofRecordClosed {
+    pub challenge_id: Pubkey,
+    pub user: Pubkey,
+    pub day_index: u32,
+    pub payer: Pubkey,
+}
+
+#[event]
+pub struct DisputeOpened {
+    pub challenge_id: Pubkey,
+    pub user: Pubkey,
+    pub day_index: u32,
+}
+
+#[event]
+pub struct DisputeResolved {
+    pub challenge_id: Pubkey,
+    pub user: Pubkey,
+    pub day_index: u32,
+    pub upheld: bool,
+    pub proof_days: u32,
+}
+
 #[event]
 pub struct ChallengeSettlementStarted {
     pub challenge_id: Pubkey,
@@ -1425,6 +4895,14 @@ pub struct ParticipantSettled {
     pub is_winner: bool,
     pub proof_days: u32,
     pub required_days: u32,
+    pub bracket: u8,
+}
+
+#[event]
+pub struct ParticipantForfeited {
+    pub challenge_id: Pubkey,
+    pub user: Pubkey,
+    pub proof_days: u32,
 }
 
 #[event]
@@ -1441,6 +4919,35 @@ pub struct ChallengeSettled {
     pub loser_count: u32,
     pub bonus_per_winner: u64,
     pub forfeited_amount: u64,
+    pub fee_amount: u64,
+    pub creator_fee_amount: u64,
+    pub sponsor_pool: u64,
+    pub bonus_pool: u64,
+    pub claim_deadline: i64,
+}
+
+#[event]
+pub struct UnclaimedSwept {
+    pub challenge_id: Pubkey,
+    pub user: Pubkey,
+    pub treasury: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct PoolsSwept {
+    pub challenge_id: Pubkey,
+    pub treasury: Pubkey,
+    pub daily_amount: u64,
+    pub sponsor_amount: u64,
+}
+
+#[event]
+pub struct PayoutAssigned {
+    pub challenge_id: Pubkey,
+    pub user: Pubkey,
+    pub is_winner: bool,
+    pub payout_amount: u64,
 }
 
 #[event]
@@ -1452,6 +4959,35 @@ pub struct PayoutClaimed {
     pub total_amount: u64,
 }
 
+#[event]
+pub struct DaySettled {
+    pub challenge_id: Pubkey,
+    pub day_index: u32,
+    pub showed_up: u32,
+    pub missed: u32,
+    pub base_daily_rate: u64,
+    pub bonus_per_person: u64,
+    pub forfeited_amount: u64,
+    pub fee_amount: u64,
+    pub creator_fee_amount: u64,
+}
+
+#[event]
+pub struct DailyPayoutClaimed {
+    pub challenge_id: Pubkey,
+    pub user: Pubkey,
+    pub from_day: u32,
+    pub to_day: u32,
+    pub amount: u64,
+}
+
+#[event]
+pub struct DailySettlementClosed {
+    pub challenge_id: Pubkey,
+    pub day_index: u32,
+    pub payer: Pubkey,
+}
+
 #[event]
 pub struct ForfeitedStakesClaimed {
     pub challenge_id: Pubkey,
@@ -1459,11 +4995,66 @@ pub struct ForfeitedStakesClaimed {
     pub amount: u64,
 }
 
+#[event]
+pub struct PlatformFeeClaimed {
+    pub challenge_id: Pubkey,
+    pub treasury: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct ParticipantLeft {
+    pub challenge_id: Pubkey,
+    pub user: Pubkey,
+    pub refund: u64,
+    pub penalty: u64,
+    pub participant_count: u32,
+}
+
+#[event]
+pub struct PrizePoolFunded {
+    pub challenge_id: Pubkey,
+    pub sponsor: Pubkey,
+    pub amount: u64,
+    pub sponsor_pool: u64,
+}
+
+#[event]
+pub struct SponsorDepositClosed {
+    pub challenge_id: Pubkey,
+    pub sponsor: Pubkey,
+    pub refunded: u64,
+}
+
+#[event]
+pub struct CreatorFeeClaimed {
+    pub challenge_id: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct ChallengePaused {
+    pub challenge_id: Pubkey,
+    pub paused_by: Pubkey,
+    pub paused_at: i64,
+}
+
+#[event]
+pub struct ChallengeResumed {
+    pub challenge_id: Pubkey,
+    pub resumed_by: Pubkey,
+    pub paused_duration: i64,
+    pub start_ts: i64,
+    pub end_ts: i64,
+}
+
 #[event]
 pub struct ChallengeCancelled {
     pub challenge_id: Pubkey,
     pub creator: Pubkey,
     pub participant_count: u32,
+    pub claim_deadline: i64,
 }
 
 #[event]
@@ -1570,4 +5161,104 @@ pub enum ProvenError {
     MaxProofsReached,
     #[msg("Escrow vault still has tokens")]
     EscrowNotEmpty,
+    #[msg("Day is outside the challenge window")]
+    DayOutOfRange,
+    #[msg("Proof already recorded for this day")]
+    ProofAlreadyRecorded,
+    #[msg("Instruction not available for this payout mode")]
+    InvalidPayoutMode,
+    #[msg("Days must be settled in order")]
+    DaySettlementOutOfOrder,
+    #[msg("Day has not ended yet")]
+    DayNotEnded,
+    #[msg("No settled days provided to claim")]
+    NoDaysToClaim,
+    #[msg("Invalid daily settlement account")]
+    InvalidDailySettlement,
+    #[msg("Day already settled")]
+    DayAlreadySettled,
+    #[msg("No proof recorded for this day")]
+    DayNotProven,
+    #[msg("Invalid dispute window")]
+    InvalidDisputeWindow,
+    #[msg("Dispute window still open")]
+    DisputeWindowOpen,
+    #[msg("Dispute window has closed")]
+    DisputeWindowClosed,
+    #[msg("Open disputes must be resolved first")]
+    DisputesPending,
+    #[msg("Invalid oracle committee")]
+    InvalidOracleCommittee,
+    #[msg("Invalid oracle threshold")]
+    InvalidOracleThreshold,
+    #[msg("Not enough oracle signatures")]
+    OracleQuorumNotMet,
+    #[msg("Oracle attestation has expired")]
+    AttestationExpired,
+    #[msg("Missing or malformed oracle attestation")]
+    InvalidAttestation,
+    #[msg("Invalid role transfer")]
+    InvalidRoleTransfer,
+    #[msg("No matching pending role transfer")]
+    NoPendingRoleTransfer,
+    #[msg("Invalid update delay")]
+    InvalidUpdateDelay,
+    #[msg("A factory update is already queued")]
+    UpdateAlreadyQueued,
+    #[msg("No factory update is queued")]
+    NoPendingUpdate,
+    #[msg("Update delay has not elapsed")]
+    UpdateNotReady,
+    #[msg("Invalid win threshold bounds")]
+    InvalidThresholdBounds,
+    #[msg("Win threshold outside the factory's allowed range")]
+    InvalidThreshold,
+    #[msg("Platform fee exceeds the maximum")]
+    FeeTooHigh,
+    #[msg("No platform fee to claim")]
+    NoPlatformFee,
+    #[msg("Platform fee not yet claimed by treasury")]
+    PlatformFeeUnclaimed,
+    #[msg("No creator fee to claim")]
+    NoCreatorFee,
+    #[msg("Creator fee not yet claimed")]
+    CreatorFeeUnclaimed,
+    #[msg("Sponsor deposits not yet refunded")]
+    SponsorRefundsPending,
+    #[msg("Invalid reward brackets")]
+    InvalidRewardBrackets,
+    #[msg("No participants to assign payouts to")]
+    NoParticipantsToAssign,
+    #[msg("Participant does not belong to this challenge")]
+    InvalidParticipant,
+    #[msg("Payout already assigned")]
+    PayoutAlreadyAssigned,
+    #[msg("Payout not yet assigned")]
+    PayoutNotAssigned,
+    #[msg("Claim window must be positive")]
+    InvalidClaimWindow,
+    #[msg("Claim deadline has not passed")]
+    ClaimDeadlineNotReached,
+    #[msg("Nothing to sweep")]
+    NothingToSweep,
+    #[msg("Early-exit penalty too high")]
+    PenaltyTooHigh,
+    #[msg("Late-join window must be shorter than the challenge")]
+    InvalidLateJoinWindow,
+    #[msg("Minimum participants cannot exceed the maximum")]
+    InvalidParticipantLimits,
+    #[msg("Challenge is full")]
+    ChallengeFull,
+    #[msg("Challenge has enough participants")]
+    MinParticipantsMet,
+    #[msg("Challenge is paused")]
+    ChallengePaused,
+    #[msg("Challenge is not paused")]
+    NotPaused,
+    #[msg("Unclaimed payouts were swept to the treasury")]
+    PayoutsSwept,
+    #[msg("Challenge does not have enough participants")]
+    MinParticipantsNotMet,
+    #[msg("Dispute outcome does not match the day's recorded proof")]
+    DisputeOutcomeMismatch,
 }
@@ -1,9 +1,11 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program, BN } from "@coral-xyz/anchor";
 import {
+  Ed25519Program,
   Keypair,
   PublicKey,
   SystemProgram,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
   LAMPORTS_PER_SOL,
 } from "@solana/web3.js";
 import {
@@ -11,10 +13,12 @@ import {
   ASSOCIATED_TOKEN_PROGRAM_ID,
   createMint,
   createAssociatedTokenAccount,
+  closeAccount,
   mintTo,
   getAccount,
 } from "@solana/spl-token";
 import { expect } from "chai";
+import { createHash } from "crypto";
 
 // IDL will be loaded by Anchor
 const IDL = require("../target/idl/proven_stake.json");
@@ -87,6 +91,269 @@ describe("proven-stake", () => {
     return ata;
   }
 
+  /**
+   * Helper: Queue a factory settings update and execute it
+   * (the test factory is initialized without an update delay)
+   */
+  async function applyFactoryUpdate(
+    dayLengthSeconds: number | null,
+    disputeWindowSeconds: number | null,
+    feeBps: number | null = null,
+    claimWindowSeconds: number | null = null
+  ) {
+    await program.methods
+      .updateFactory(
+        dayLengthSeconds === null ? null : new BN(dayLengthSeconds),
+        disputeWindowSeconds === null ? null : new BN(disputeWindowSeconds),
+        null,
+        null,
+        null,
+        feeBps,
+        null,
+        claimWindowSeconds === null ? null : new BN(claimWindowSeconds)
+      )
+      .accountsPartial({
+        authority: authority.publicKey,
+        factory: factoryPDA,
+      })
+      .signers([authority])
+      .rpc();
+    await program.methods
+      .executePendingUpdate()
+      .accountsPartial({
+        authority: authority.publicKey,
+        factory: factoryPDA,
+      })
+      .signers([authority])
+      .rpc();
+  }
+
+  /**
+   * Helper: create_challenge params, defaulting to a plain 80% threshold challenge
+   */
+  function challengeParams(overrides: Record<string, any> = {}) {
+    return {
+      payoutMode: { threshold: {} },
+      thresholdBps: WIN_THRESHOLD_BPS,
+      creatorFeeBps: 0,
+      rewardBrackets: [],
+      earlyExitPenaltyBps: 0,
+      lateJoinDays: 0,
+      maxParticipants: 0,
+      minParticipants: 0,
+      ...overrides,
+    };
+  }
+
+  /**
+   * Helper: Record settled participants' payout entitlements
+   */
+  async function assignPayouts(
+    challengeId: string,
+    challenge: PublicKey,
+    participants: PublicKey[]
+  ) {
+    await program.methods
+      .assignPayouts(challengeId)
+      .accountsPartial({
+        factory: factoryPDA,
+        challenge,
+      })
+      .remainingAccounts(
+        participants.map((pubkey) => ({ pubkey, isSigner: false, isWritable: true }))
+      )
+      .rpc();
+  }
+
+  /**
+   * Helper: Create a challenge starting in 2 seconds, as `creator`
+   */
+  async function createTestChallenge(
+    challengeId: string,
+    totalDays: number,
+    overrides: Record<string, any> = {}
+  ) {
+    const [challengePDA] = getChallengePDA(challengeId, factoryPDA);
+    await program.methods
+      .createChallenge(
+        challengeId,
+        new BN(STAKE_AMOUNT),
+        totalDays,
+        new BN(getFutureTimestamp(2)),
+        challengeParams(overrides)
+      )
+      .accountsPartial({
+        creator: creator.publicKey,
+        factory: factoryPDA,
+        challenge: challengePDA,
+        tokenMint: usdcMint,
+        escrowVault: await getEscrowVault(challengePDA),
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      })
+      .signers([creator])
+      .rpc();
+  }
+
+  interface ChallengeUser {
+    user: Keypair;
+    participant: PublicKey;
+    tokenAccount: PublicKey;
+  }
+
+  interface SettledChallenge {
+    challengeId: string;
+    challengePDA: PublicKey;
+    escrowVault: PublicKey;
+    users: ChallengeUser[];
+    join(user: ChallengeUser): Promise<string>;
+    recordProof(participant: PublicKey, day: number): Promise<string>;
+    claimPayout(user: ChallengeUser): Promise<string>;
+  }
+
+  /**
+   * Helper: Run a challenge through to assigned payouts
+   * One user is funded with a stake per `proofsByUser` entry, joins on their
+   * `joinDays` day (before start by default) and proves the listed days.
+   * `afterCreate` runs before anyone joins and `onDay` after each day's proofs.
+   */
+  async function runSettledChallenge(
+    params: {
+      challengeId: string;
+      totalDays?: number;
+      overrides?: Record<string, any>;
+      joinDays?: number[];
+      afterCreate?: (run: SettledChallenge) => Promise<unknown>;
+      onDay?: (day: number, run: SettledChallenge) => Promise<unknown>;
+    },
+    proofsByUser: number[][]
+  ): Promise<SettledChallenge> {
+    const { challengeId, totalDays = 1, overrides = {}, joinDays = [] } = params;
+    const [challengePDA] = getChallengePDA(challengeId, factoryPDA);
+    const escrowVault = await getEscrowVault(challengePDA);
+
+    const users: ChallengeUser[] = [];
+    for (const _ of proofsByUser) {
+      const user = Keypair.generate();
+      await airdrop(user.publicKey);
+      users.push({
+        user,
+        participant: getParticipantPDA(challengePDA, user.publicKey)[0],
+        tokenAccount: await setupTokenAccount(user, STAKE_AMOUNT),
+      });
+    }
+
+    const oracleAccounts = {
+      oracle: oracle.publicKey,
+      factory: factoryPDA,
+      challenge: challengePDA,
+    };
+    const run: SettledChallenge = {
+      challengeId,
+      challengePDA,
+      escrowVault,
+      users,
+      join: ({ user, participant, tokenAccount }) =>
+        program.methods
+          .joinChallenge(challengeId)
+          .accountsPartial({
+            user: user.publicKey,
+            factory: factoryPDA,
+            challenge: challengePDA,
+            participant,
+            userTokenAccount: tokenAccount,
+            escrowVault,
+            systemProgram: SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([user])
+          .rpc(),
+      recordProof: (participant, day) =>
+        program.methods
+          .recordProof(challengeId, day, proofHash(`proof-${day}`))
+          .accountsPartial({
+            ...oracleAccounts,
+            participant,
+            proofRecord: getProofRecordPDA(participant, day)[0],
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([oracle])
+          .rpc(),
+      claimPayout: ({ user, participant, tokenAccount }) =>
+        program.methods
+          .claimPayout(challengeId)
+          .accountsPartial({
+            user: user.publicKey,
+            factory: factoryPDA,
+            challenge: challengePDA,
+            participant,
+            userTokenAccount: tokenAccount,
+            escrowVault,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([user])
+          .rpc(),
+    };
+
+    await createTestChallenge(challengeId, totalDays, overrides);
+    await params.afterCreate?.(run);
+
+    const joinDay = (index: number) => joinDays[index] ?? 0;
+    const { dayLengthSeconds } = await (program.account as any).challengeEscrow.fetch(
+      challengePDA
+    );
+    const dayLengthMs = dayLengthSeconds.toNumber() * 1000;
+
+    for (const [index, user] of users.entries()) {
+      if (joinDay(index) === 0) {
+        await run.join(user);
+      }
+    }
+
+    await sleep(2500);
+    for (let day = 0; day < totalDays; day++) {
+      if (day > 0) {
+        await sleep(dayLengthMs);
+      }
+      for (const [index, user] of users.entries()) {
+        if (day > 0 && joinDay(index) === day) {
+          await run.join(user);
+        }
+        if (proofsByUser[index].includes(day)) {
+          await run.recordProof(user.participant, day);
+        }
+      }
+      await params.onDay?.(day, run);
+    }
+    await sleep(dayLengthMs + 2000);
+
+    await program.methods
+      .settleChallenge(challengeId)
+      .accountsPartial(oracleAccounts)
+      .signers([oracle])
+      .rpc();
+    for (const { participant } of users) {
+      await program.methods
+        .settleParticipant(challengeId)
+        .accountsPartial({ ...oracleAccounts, participant })
+        .signers([oracle])
+        .rpc();
+    }
+    await program.methods
+      .finalizeSettlement(challengeId)
+      .accountsPartial(oracleAccounts)
+      .signers([oracle])
+      .rpc();
+    await assignPayouts(
+      challengeId,
+      challengePDA,
+      users.map(({ participant }) => participant)
+    );
+
+    return run;
+  }
+
   /**
    * Helper: Get factory PDA
    */
@@ -123,6 +390,56 @@ describe("proven-stake", () => {
     );
   }
 
+  /**
+   * Helper: Get proof record PDA
+   */
+  function getProofRecordPDA(
+    participant: PublicKey,
+    dayIndex: number
+  ): [PublicKey, number] {
+    const dayBuf = Buffer.alloc(4);
+    dayBuf.writeUInt32LE(dayIndex);
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("proof"), participant.toBuffer(), dayBuf],
+      PROGRAM_ID
+    );
+  }
+
+  /**
+   * Helper: 32-byte commitment to a proof (stands in for media + submission hash)
+   */
+  function proofHash(label: string): number[] {
+    return Array.from(createHash("sha256").update(label).digest());
+  }
+
+  /**
+   * Helper: Get sponsor deposit PDA
+   */
+  function getSponsorDepositPDA(
+    challenge: PublicKey,
+    sponsor: PublicKey
+  ): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("sponsor"), challenge.toBuffer(), sponsor.toBuffer()],
+      PROGRAM_ID
+    );
+  }
+
+  /**
+   * Helper: Get daily settlement PDA
+   */
+  function getDailySettlementPDA(
+    challenge: PublicKey,
+    dayIndex: number
+  ): [PublicKey, number] {
+    const dayBuf = Buffer.alloc(4);
+    dayBuf.writeUInt32LE(dayIndex);
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("daily"), challenge.toBuffer(), dayBuf],
+      PROGRAM_ID
+    );
+  }
+
   /**
    * Helper: Get escrow vault ATA
    */
@@ -199,7 +516,7 @@ describe("proven-stake", () => {
   describe("Factory", () => {
     it("should initialize factory", async () => {
       await program.methods
-        .initializeFactory()
+        .initializeFactory(new BN(0))
         .accountsPartial({
           authority: authority.publicKey,
           treasury: treasury.publicKey,
@@ -222,12 +539,14 @@ describe("proven-stake", () => {
       );
       expect(factory.oracle.toBase58()).to.equal(oracle.publicKey.toBase58());
       expect(factory.challengeCount.toNumber()).to.equal(0);
+      expect(factory.minThresholdBps).to.equal(5000);
+      expect(factory.maxThresholdBps).to.equal(10000);
     });
 
     it("should fail to initialize factory twice", async () => {
       try {
         await program.methods
-          .initializeFactory()
+          .initializeFactory(new BN(0))
           .accountsPartial({
             authority: authority.publicKey,
             treasury: treasury.publicKey,
@@ -245,11 +564,139 @@ describe("proven-stake", () => {
     });
 
     it("should update factory settings", async () => {
+      await applyFactoryUpdate(12 * 60 * 60, null);
+
+      const factory = await (program.account as any).escrowFactory.fetch(
+        factoryPDA
+      );
+      expect(factory.dayLengthSeconds.toNumber()).to.equal(12 * 60 * 60);
+      expect(factory.pendingUpdate).to.be.null;
+
+      // Restore original day length
+      await applyFactoryUpdate(24 * 60 * 60, null);
+    });
+
+    it("should fail to update factory with wrong authority", async () => {
+      try {
+        await program.methods
+          .updateFactory(new BN(60), null, null, null, null, null, null, null)
+          .accountsPartial({
+            authority: creator.publicKey, // Wrong authority
+            factory: factoryPDA,
+          })
+          .signers([creator])
+          .rpc();
+        expect.fail("Should have thrown error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("Unauthorized");
+      }
+    });
+
+    it("should reject inverted threshold bounds when queuing", async () => {
+      for (const [minBps, maxBps] of [
+        [9000, 8000], // inverted within the update
+        [null, 4000], // below the current minimum
+      ] as [number | null, number | null][]) {
+        try {
+          await program.methods
+            .updateFactory(null, null, null, minBps, maxBps, null, null, null)
+            .accountsPartial({
+              authority: authority.publicKey,
+              factory: factoryPDA,
+            })
+            .signers([authority])
+            .rpc();
+          expect.fail("Should have thrown error");
+        } catch (err: any) {
+          expect(err.toString()).to.include("InvalidThresholdBounds");
+        }
+      }
+
+      const factory = await (program.account as any).escrowFactory.fetch(factoryPDA);
+      expect(factory.pendingUpdate).to.be.null;
+    });
+
+    it("should hold queued updates until the delay has passed", async () => {
+      const UPDATE_DELAY_SECONDS = 2;
+      const queue = (dayLength: number | null, delay: number | null) =>
+        program.methods
+          .updateFactory(
+            dayLength === null ? null : new BN(dayLength),
+            null,
+            delay === null ? null : new BN(delay),
+            null,
+            null,
+            null,
+            null,
+            null
+          )
+          .accountsPartial({
+            authority: authority.publicKey,
+            factory: factoryPDA,
+          })
+          .signers([authority])
+          .rpc();
+      const execute = () =>
+        program.methods
+          .executePendingUpdate()
+          .accountsPartial({
+            authority: authority.publicKey,
+            factory: factoryPDA,
+          })
+          .signers([authority])
+          .rpc();
+
+      // Turn on the timelock (takes effect immediately while the delay is 0)
+      await queue(null, UPDATE_DELAY_SECONDS);
+      await execute();
+
+      await queue(60, null);
+      let factory = await (program.account as any).escrowFactory.fetch(
+        factoryPDA
+      );
+      expect(factory.pendingUpdate.dayLengthSeconds.toNumber()).to.equal(60);
+      expect(factory.dayLengthSeconds.toNumber()).to.equal(24 * 60 * 60);
+
+      try {
+        await execute();
+        expect.fail("Should have thrown error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("UpdateNotReady");
+      }
+
+      try {
+        await queue(120, null);
+        expect.fail("Should have thrown error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("UpdateAlreadyQueued");
+      }
+
+      await program.methods
+        .cancelPendingUpdate()
+        .accountsPartial({
+          authority: authority.publicKey,
+          factory: factoryPDA,
+        })
+        .signers([authority])
+        .rpc();
+      factory = await (program.account as any).escrowFactory.fetch(factoryPDA);
+      expect(factory.pendingUpdate).to.be.null;
+      expect(factory.dayLengthSeconds.toNumber()).to.equal(24 * 60 * 60);
+
+      // Turn the timelock back off for the rest of the suite
+      await queue(null, 0);
+      await sleep((UPDATE_DELAY_SECONDS + 1) * 1000);
+      await execute();
+      factory = await (program.account as any).escrowFactory.fetch(factoryPDA);
+      expect(factory.updateDelaySeconds.toNumber()).to.equal(0);
+    });
+
+    it("should rotate treasury with a two-step transfer", async () => {
       const newTreasury = Keypair.generate();
       await airdrop(newTreasury.publicKey, 1);
 
       await program.methods
-        .updateFactory(null, newTreasury.publicKey, null, null)
+        .proposeRoleTransfer({ treasury: {} }, newTreasury.publicKey)
         .accountsPartial({
           authority: authority.publicKey,
           factory: factoryPDA,
@@ -257,30 +704,112 @@ describe("proven-stake", () => {
         .signers([authority])
         .rpc();
 
-      const factory = await (program.account as any).escrowFactory.fetch(
+      // Proposal alone changes nothing
+      let factory = await (program.account as any).escrowFactory.fetch(
         factoryPDA
       );
+      expect(factory.treasury.toBase58()).to.equal(treasury.publicKey.toBase58());
+      expect(factory.pendingTreasury.toBase58()).to.equal(
+        newTreasury.publicKey.toBase58()
+      );
+
+      // Only the proposed key can accept
+      try {
+        await program.methods
+          .acceptRoleTransfer({ treasury: {} })
+          .accountsPartial({
+            newHolder: creator.publicKey,
+            factory: factoryPDA,
+          })
+          .signers([creator])
+          .rpc();
+        expect.fail("Should have thrown error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("NoPendingRoleTransfer");
+      }
+
+      await program.methods
+        .acceptRoleTransfer({ treasury: {} })
+        .accountsPartial({
+          newHolder: newTreasury.publicKey,
+          factory: factoryPDA,
+        })
+        .signers([newTreasury])
+        .rpc();
+
+      factory = await (program.account as any).escrowFactory.fetch(factoryPDA);
       expect(factory.treasury.toBase58()).to.equal(
         newTreasury.publicKey.toBase58()
       );
+      expect(factory.pendingTreasury).to.be.null;
 
       // Restore original treasury
       await program.methods
-        .updateFactory(null, treasury.publicKey, null, null)
+        .proposeRoleTransfer({ treasury: {} }, treasury.publicKey)
         .accountsPartial({
           authority: authority.publicKey,
           factory: factoryPDA,
         })
         .signers([authority])
         .rpc();
+      await program.methods
+        .acceptRoleTransfer({ treasury: {} })
+        .accountsPartial({
+          newHolder: treasury.publicKey,
+          factory: factoryPDA,
+        })
+        .signers([treasury])
+        .rpc();
     });
 
-    it("should fail to update factory with wrong authority", async () => {
+    it("should cancel a pending authority transfer", async () => {
+      const typo = Keypair.generate();
+
+      await program.methods
+        .proposeRoleTransfer({ authority: {} }, typo.publicKey)
+        .accountsPartial({
+          authority: authority.publicKey,
+          factory: factoryPDA,
+        })
+        .signers([authority])
+        .rpc();
+      await program.methods
+        .cancelRoleTransfer({ authority: {} })
+        .accountsPartial({
+          authority: authority.publicKey,
+          factory: factoryPDA,
+        })
+        .signers([authority])
+        .rpc();
+
       try {
         await program.methods
-          .updateFactory(null, treasury.publicKey, null, null)
+          .acceptRoleTransfer({ authority: {} })
           .accountsPartial({
-            authority: creator.publicKey, // Wrong authority
+            newHolder: typo.publicKey,
+            factory: factoryPDA,
+          })
+          .signers([typo])
+          .rpc();
+        expect.fail("Should have thrown error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("NoPendingRoleTransfer");
+      }
+
+      const factory = await (program.account as any).escrowFactory.fetch(
+        factoryPDA
+      );
+      expect(factory.authority.toBase58()).to.equal(
+        authority.publicKey.toBase58()
+      );
+    });
+
+    it("should only let the authority renounce", async () => {
+      try {
+        await program.methods
+          .renounceAuthority()
+          .accountsPartial({
+            authority: creator.publicKey,
             factory: factoryPDA,
           })
           .signers([creator])
@@ -314,7 +843,8 @@ describe("proven-stake", () => {
           challengeId,
           new BN(STAKE_AMOUNT),
           TOTAL_DAYS,
-          new BN(startTs)
+          new BN(startTs),
+          challengeParams()
         )
         .accountsPartial({
           creator: creator.publicKey,
@@ -342,6 +872,9 @@ describe("proven-stake", () => {
       expect(challenge.thresholdBps).to.equal(WIN_THRESHOLD_BPS);
       expect(challenge.status.created).to.not.be.undefined;
       expect(challenge.participantCount).to.equal(0);
+      expect(challenge.oracle.toBase58()).to.equal(oracle.publicKey.toBase58());
+      expect(challenge.oracleThreshold).to.equal(1);
+      expect(challenge.treasury.toBase58()).to.equal(treasury.publicKey.toBase58());
 
       // Verify factory count incremented
       const factory = await (program.account as any).escrowFactory.fetch(
@@ -362,7 +895,8 @@ describe("proven-stake", () => {
             badChallengeId,
             new BN(STAKE_AMOUNT),
             TOTAL_DAYS,
-            new BN(pastStartTs)
+            new BN(pastStartTs),
+            challengeParams()
           )
           .accountsPartial({
             creator: creator.publicKey,
@@ -396,7 +930,8 @@ describe("proven-stake", () => {
             zeroStakeChallengeId,
             new BN(0), // Zero stake
             TOTAL_DAYS,
-            new BN(getFutureTimestamp(60))
+            new BN(getFutureTimestamp(60)),
+            challengeParams()
           )
           .accountsPartial({
             creator: creator.publicKey,
@@ -415,6 +950,81 @@ describe("proven-stake", () => {
         expect(err.toString()).to.include("InvalidAmount");
       }
     });
+
+    describe("Win threshold", () => {
+      // Creates a challenge and returns the `ChallengeCreated` event it emitted
+      const createWithThreshold = async (
+        id: string,
+        totalDays: number,
+        thresholdBps: number
+      ) => {
+        const [pda] = getChallengePDA(id, factoryPDA);
+        const sig = await program.methods
+          .createChallenge(
+            id,
+            new BN(STAKE_AMOUNT),
+            totalDays,
+            new BN(getFutureTimestamp(3600)),
+            challengeParams({ thresholdBps })
+          )
+          .accountsPartial({
+            creator: creator.publicKey,
+            factory: factoryPDA,
+            challenge: pda,
+            tokenMint: usdcMint,
+            escrowVault: await getEscrowVault(pda),
+            systemProgram: SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          })
+          .signers([creator])
+          .rpc({ commitment: "confirmed" });
+
+        const tx = await provider.connection.getTransaction(sig, {
+          commitment: "confirmed",
+          maxSupportedTransactionVersion: 0,
+        });
+        const parser = new anchor.EventParser(program.programId, program.coder);
+        const events = [...parser.parseLogs(tx!.meta!.logMessages!)];
+        return events.find((e) => e.name === "challengeCreated")!.data as any;
+      };
+
+      it("should reject thresholds outside the factory bounds", async () => {
+        for (const [id, bps] of [
+          ["threshold-low", 4999],
+          ["threshold-high", 10001],
+        ] as [string, number][]) {
+          try {
+            await createWithThreshold(id, TOTAL_DAYS, bps);
+            expect.fail("Should have thrown error");
+          } catch (err: any) {
+            expect(err.toString()).to.include("InvalidThreshold");
+          }
+        }
+      });
+
+      it("should round required days up at the edges", async () => {
+        const cases: [string, number, number, number][] = [
+          // [id, total days, threshold bps, expected required days]
+          ["threshold-3d-5000", 3, 5000, 2], // 1.5 -> 2
+          ["threshold-3d-6666", 3, 6666, 2], // 1.9998 -> 2
+          ["threshold-3d-6667", 3, 6667, 3], // 2.0001 -> 3
+          ["threshold-3d-10000", 3, 10000, 3],
+          ["threshold-1d-5000", 1, 5000, 1], // 0.5 -> 1
+          ["threshold-10d-8000", 10, 8000, 8],
+        ];
+
+        for (const [id, totalDays, bps, expected] of cases) {
+          const event = await createWithThreshold(id, totalDays, bps);
+          expect(event.thresholdBps).to.equal(bps);
+          expect(event.requiredDays).to.equal(expected);
+
+          const [pda] = getChallengePDA(id, factoryPDA);
+          const challenge = await (program.account as any).challengeEscrow.fetch(pda);
+          expect(challenge.thresholdBps).to.equal(bps);
+        }
+      });
+    });
   });
 
   // ============================================================
@@ -440,7 +1050,8 @@ describe("proven-stake", () => {
           challengeId,
           new BN(STAKE_AMOUNT),
           TOTAL_DAYS,
-          new BN(startTs)
+          new BN(startTs),
+          challengeParams()
         )
         .accountsPartial({
           creator: creator.publicKey,
@@ -578,7 +1189,8 @@ describe("proven-stake", () => {
           challengeId,
           new BN(STAKE_AMOUNT),
           TOTAL_DAYS,
-          new BN(startTs)
+          new BN(startTs),
+          challengeParams()
         )
         .accountsPartial({
           creator: creator.publicKey,
@@ -618,12 +1230,14 @@ describe("proven-stake", () => {
 
     it("should allow oracle to record proof", async () => {
       await program.methods
-        .recordProof(challengeId)
+        .recordProof(challengeId, 0, proofHash("proof-0"))
         .accountsPartial({
           oracle: oracle.publicKey,
           factory: factoryPDA,
           challenge: challengePDA,
           participant: participantPDA,
+          proofRecord: getProofRecordPDA(participantPDA, 0)[0],
+          systemProgram: SystemProgram.programId,
         })
         .signers([oracle])
         .rpc();
@@ -639,41 +1253,152 @@ describe("proven-stake", () => {
         challengePDA
       );
       expect(challenge.status.started).to.not.be.undefined;
+
+      // Verify the proof commitment was stored for day 0
+      const proofRecord = await (program.account as any).proofRecord.fetch(
+        getProofRecordPDA(participantPDA, 0)[0]
+      );
+      expect(proofRecord.user.toBase58()).to.equal(user3.publicKey.toBase58());
+      expect(proofRecord.dayIndex).to.equal(0);
+      expect(proofRecord.proofHash).to.deep.equal(proofHash("proof-0"));
     });
 
-    it("should allow multiple proofs", async () => {
-      // Record 7 more proofs (total 8)
-      for (let i = 0; i < 7; i++) {
+    it("should reject a second proof for the same day", async () => {
+      try {
         await program.methods
-          .recordProof(challengeId)
+          .recordProof(challengeId, 0, proofHash("proof-0"))
           .accountsPartial({
             oracle: oracle.publicKey,
             factory: factoryPDA,
             challenge: challengePDA,
             participant: participantPDA,
+            proofRecord: getProofRecordPDA(participantPDA, 0)[0],
+            systemProgram: SystemProgram.programId,
           })
           .signers([oracle])
           .rpc();
+        expect.fail("Should have thrown error");
+      } catch (err: any) {
+        // The day's proof record already exists
+        expect(err.toString()).to.include("already in use");
       }
 
       const participant = await (program.account as any).participant.fetch(
         participantPDA
       );
-      expect(participant.proofDays).to.equal(8);
+      expect(participant.proofDays).to.equal(1);
+      // Day 0 bit set
+      expect(participant.proofBitmap[0] & 1).to.equal(1);
     });
 
-    it("should fail when non-oracle tries to record proof", async () => {
+    it("should allow multiple proofs", async () => {
+      // Short days so several have begun; the factory day length is restored after
+      await applyFactoryUpdate(3, null);
+      const multiId = "proof-multi-001";
+      const [multiPDA] = getChallengePDA(multiId, factoryPDA);
+      const multiVault = await getEscrowVault(multiPDA);
+
       try {
         await program.methods
-          .recordProof(challengeId)
+          .createChallenge(
+            multiId,
+            new BN(STAKE_AMOUNT),
+            TOTAL_DAYS,
+            new BN(getFutureTimestamp(2)),
+            challengeParams()
+          )
           .accountsPartial({
-            oracle: user1.publicKey, // Not the oracle
+            creator: creator.publicKey,
             factory: factoryPDA,
-            challenge: challengePDA,
-            participant: participantPDA,
-          })
-          .signers([user1])
-          .rpc();
+            challenge: multiPDA,
+            tokenMint: usdcMint,
+            escrowVault: multiVault,
+            systemProgram: SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          })
+          .signers([creator])
+          .rpc();
+      } finally {
+        await applyFactoryUpdate(24 * 60 * 60, null);
+      }
+
+      const [multiParticipantPDA] = getParticipantPDA(multiPDA, user3.publicKey);
+      await program.methods
+        .joinChallenge(multiId)
+        .accountsPartial({
+          user: user3.publicKey,
+          factory: factoryPDA,
+          challenge: multiPDA,
+          participant: multiParticipantPDA,
+          userTokenAccount: userTokenAccount,
+          escrowVault: multiVault,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user3])
+        .rpc();
+
+      // Wait until day 2 has begun, then record days 0-2
+      await sleep(9000);
+      for (let day = 0; day < 3; day++) {
+        await program.methods
+          .recordProof(multiId, day, proofHash(`multi-${day}`))
+          .accountsPartial({
+            oracle: oracle.publicKey,
+            factory: factoryPDA,
+            challenge: multiPDA,
+            participant: multiParticipantPDA,
+            proofRecord: getProofRecordPDA(multiParticipantPDA, day)[0],
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([oracle])
+          .rpc();
+      }
+
+      const participant = await (program.account as any).participant.fetch(
+        multiParticipantPDA
+      );
+      expect(participant.proofDays).to.equal(3);
+    });
+
+    it("should reject proofs for future or out-of-range days", async () => {
+      for (const day of [1, TOTAL_DAYS]) {
+        try {
+          await program.methods
+            .recordProof(challengeId, day, proofHash(`proof-${day}`))
+            .accountsPartial({
+              oracle: oracle.publicKey,
+              factory: factoryPDA,
+              challenge: challengePDA,
+              participant: participantPDA,
+              proofRecord: getProofRecordPDA(participantPDA, day)[0],
+              systemProgram: SystemProgram.programId,
+            })
+            .signers([oracle])
+            .rpc();
+          expect.fail("Should have thrown error");
+        } catch (err: any) {
+          expect(err.toString()).to.include("DayOutOfRange");
+        }
+      }
+    });
+
+    it("should fail when non-oracle tries to record proof", async () => {
+      try {
+        await program.methods
+          // Day 1 so the proof record PDA is still free and the oracle check is reached
+          .recordProof(challengeId, 1, proofHash("proof-1"))
+          .accountsPartial({
+            oracle: user1.publicKey, // Not the oracle
+            factory: factoryPDA,
+            challenge: challengePDA,
+            participant: participantPDA,
+            proofRecord: getProofRecordPDA(participantPDA, 1)[0],
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([user1])
+          .rpc();
         expect.fail("Should have thrown error");
       } catch (err: any) {
         expect(err.toString()).to.include("InvalidOracle");
@@ -715,7 +1440,8 @@ describe("proven-stake", () => {
           challengeId,
           new BN(STAKE_AMOUNT),
           1, // 1 day - so 80% threshold = 1 proof needed
-          new BN(startTs)
+          new BN(startTs),
+          challengeParams()
         )
         .accountsPartial({
           creator: creator.publicKey,
@@ -773,12 +1499,14 @@ describe("proven-stake", () => {
 
       // Record proof only for winner
       await program.methods
-        .recordProof(challengeId)
+        .recordProof(challengeId, 0, proofHash("proof-0"))
         .accountsPartial({
           oracle: oracle.publicKey,
           factory: factoryPDA,
           challenge: challengePDA,
           participant: participant1PDA,
+          proofRecord: getProofRecordPDA(participant1PDA, 0)[0],
+          systemProgram: SystemProgram.programId,
         })
         .signers([oracle])
         .rpc();
@@ -789,6 +1517,64 @@ describe("proven-stake", () => {
       // This is a simplification - in production tests, use proper time manipulation
     });
 
+    it("should let a participant forfeit and close their account early", async () => {
+      await program.methods
+        .forfeit(challengeId)
+        .accountsPartial({
+          user: loser.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+          participant: participant2PDA,
+        })
+        .signers([loser])
+        .rpc();
+
+      const participant = await (program.account as any).participant.fetch(
+        participant2PDA
+      );
+      expect(participant.forfeited).to.be.true;
+      expect(participant.isSettled).to.be.true;
+      expect(participant.isWinner).to.be.false;
+
+      try {
+        await program.methods
+          .forfeit(challengeId)
+          .accountsPartial({
+            user: loser.publicKey,
+            factory: factoryPDA,
+            challenge: challengePDA,
+            participant: participant2PDA,
+          })
+          .signers([loser])
+          .rpc();
+        expect.fail("Should have thrown error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("AlreadySettled");
+      }
+
+      await program.methods
+        .closeParticipant(challengeId)
+        .accountsPartial({
+          authority: loser.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+          participant: participant2PDA,
+          destination: loser.publicKey,
+        })
+        .signers([loser])
+        .rpc();
+
+      const participantInfo = await provider.connection.getAccountInfo(participant2PDA);
+      expect(participantInfo).to.be.null;
+
+      const challenge = await (program.account as any).challengeEscrow.fetch(
+        challengePDA
+      );
+      expect(challenge.loserCount).to.equal(1);
+      expect(challenge.participantCount).to.equal(2);
+      expect(challenge.activeParticipants).to.equal(1);
+    });
+
     it("should settle challenge (mark as ended)", async () => {
       // Note: In a real test, you'd wait for end_ts to pass
       // For this test, we'll catch the expected error if time hasn't passed
@@ -842,7 +1628,8 @@ describe("proven-stake", () => {
           challengeId,
           new BN(STAKE_AMOUNT),
           TOTAL_DAYS,
-          new BN(startTs)
+          new BN(startTs),
+          challengeParams()
         )
         .accountsPartial({
           creator: creator.publicKey,
@@ -954,25 +1741,25 @@ describe("proven-stake", () => {
       }
     });
 
-    it("should fail for non-creator to cancel challenge", async () => {
-      // Create a new challenge for this test
-      const newChallengeId = "cancel-auth-test-001";
-      const [newChallengePDA] = getChallengePDA(newChallengeId, factoryPDA);
-      const newEscrowVault = await getEscrowVault(newChallengePDA);
+    it("should reject joins beyond max participants", async () => {
+      const capId = "cancel-cap-test-001";
+      const [capChallengePDA] = getChallengePDA(capId, factoryPDA);
+      const capEscrowVault = await getEscrowVault(capChallengePDA);
 
       await program.methods
         .createChallenge(
-          newChallengeId,
+          capId,
           new BN(STAKE_AMOUNT),
           TOTAL_DAYS,
-          new BN(getFutureTimestamp(300))
+          new BN(getFutureTimestamp(300)),
+          challengeParams({ maxParticipants: 1 })
         )
         .accountsPartial({
           creator: creator.publicKey,
           factory: factoryPDA,
-          challenge: newChallengePDA,
+          challenge: capChallengePDA,
           tokenMint: usdcMint,
-          escrowVault: newEscrowVault,
+          escrowVault: capEscrowVault,
           systemProgram: SystemProgram.programId,
           tokenProgram: TOKEN_PROGRAM_ID,
           associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
@@ -980,216 +1767,367 @@ describe("proven-stake", () => {
         .signers([creator])
         .rpc();
 
-      try {
-        await program.methods
-          .cancelChallenge(newChallengeId)
+      const first = Keypair.generate();
+      const second = Keypair.generate();
+      await Promise.all([airdrop(first.publicKey), airdrop(second.publicKey)]);
+
+      const join = async (user: Keypair) =>
+        program.methods
+          .joinChallenge(capId)
           .accountsPartial({
-            creator: user1.publicKey, // Not the creator
+            user: user.publicKey,
             factory: factoryPDA,
-            challenge: newChallengePDA,
+            challenge: capChallengePDA,
+            participant: getParticipantPDA(capChallengePDA, user.publicKey)[0],
+            userTokenAccount: await setupTokenAccount(user, STAKE_AMOUNT),
+            escrowVault: capEscrowVault,
+            systemProgram: SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
           })
-          .signers([user1])
+          .signers([user])
           .rpc();
+
+      await join(first);
+      try {
+        await join(second);
         expect.fail("Should have thrown error");
       } catch (err: any) {
-        expect(err.toString()).to.include("Unauthorized");
+        expect(err.toString()).to.include("ChallengeFull");
       }
     });
-  });
-
-  // ============================================================
-  // REWARDS TESTS (END-TO-END PAYOUTS)
-  // ============================================================
 
-  describe("Rewards (Payouts + Forfeits)", () => {
-    const DAY_LENGTH_SECONDS = 3;
+    it("should let anyone cancel an undersubscribed challenge after start", async () => {
+      const minId = "cancel-min-test-001";
+      const [minChallengePDA] = getChallengePDA(minId, factoryPDA);
+      const minEscrowVault = await getEscrowVault(minChallengePDA);
 
-    before(async () => {
-      // Shorten "day length" so challenges can end quickly on localnet.
       await program.methods
-        .updateFactory(null, null, null, new BN(DAY_LENGTH_SECONDS))
+        .createChallenge(
+          minId,
+          new BN(STAKE_AMOUNT),
+          TOTAL_DAYS,
+          new BN(getFutureTimestamp(3)),
+          challengeParams({ minParticipants: 2 })
+        )
         .accountsPartial({
-          authority: authority.publicKey,
+          creator: creator.publicKey,
           factory: factoryPDA,
+          challenge: minChallengePDA,
+          tokenMint: usdcMint,
+          escrowVault: minEscrowVault,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         })
-        .signers([authority])
+        .signers([creator])
         .rpc();
-    });
 
-    after(async () => {
-      // Restore default day length (86400) so future local runs behave normally.
+      const loneUser = Keypair.generate();
+      await airdrop(loneUser.publicKey);
+      const loneTokenAccount = await setupTokenAccount(loneUser, STAKE_AMOUNT);
+      const [lonePDA] = getParticipantPDA(minChallengePDA, loneUser.publicKey);
+
       await program.methods
-        .updateFactory(null, null, null, new BN(24 * 60 * 60))
+        .joinChallenge(minId)
         .accountsPartial({
-          authority: authority.publicKey,
+          user: loneUser.publicKey,
           factory: factoryPDA,
+          challenge: minChallengePDA,
+          participant: lonePDA,
+          userTokenAccount: loneTokenAccount,
+          escrowVault: minEscrowVault,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
         })
-        .signers([authority])
+        .signers([loneUser])
         .rpc();
-    });
 
-    it("should pay a winner stake + bonus (mixed winners/losers)", async () => {
-      const challengeId = "rewards-mixed-001";
-      const [challengePDA] = getChallengePDA(challengeId, factoryPDA);
-      const escrowVault = await getEscrowVault(challengePDA);
+      const cancelUndersubscribed = () =>
+        program.methods
+          .cancelUndersubscribed(minId)
+          .accountsPartial({
+            factory: factoryPDA,
+            challenge: minChallengePDA,
+          })
+          .rpc();
 
-      const winner = Keypair.generate();
-      const loser = Keypair.generate();
-      await Promise.all([airdrop(winner.publicKey), airdrop(loser.publicKey)]);
+      try {
+        await cancelUndersubscribed();
+        expect.fail("Should have thrown error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("ChallengeNotStarted");
+      }
 
-      const winnerTokenAccount = await setupTokenAccount(winner, STAKE_AMOUNT * 3);
-      const loserTokenAccount = await setupTokenAccount(loser, STAKE_AMOUNT * 3);
+      await sleep(4000);
+      await cancelUndersubscribed();
 
-      const startTs = getFutureTimestamp(2);
+      const challenge = await (program.account as any).challengeEscrow.fetch(
+        minChallengePDA
+      );
+      expect(challenge.status.cancelled).to.not.be.undefined;
 
       await program.methods
-        .createChallenge(challengeId, new BN(STAKE_AMOUNT), 1, new BN(startTs))
+        .claimRefund(minId)
         .accountsPartial({
-          creator: creator.publicKey,
+          user: loneUser.publicKey,
           factory: factoryPDA,
-          challenge: challengePDA,
-          tokenMint: usdcMint,
-          escrowVault: escrowVault,
-          systemProgram: SystemProgram.programId,
+          challenge: minChallengePDA,
+          participant: lonePDA,
+          userTokenAccount: loneTokenAccount,
+          escrowVault: minEscrowVault,
           tokenProgram: TOKEN_PROGRAM_ID,
-          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         })
-        .signers([creator])
+        .signers([loneUser])
         .rpc();
 
-      const [winnerPDA] = getParticipantPDA(challengePDA, winner.publicKey);
-      const [loserPDA] = getParticipantPDA(challengePDA, loser.publicKey);
+      const balance = await getAccount(provider.connection, loneTokenAccount);
+      expect(Number(balance.amount)).to.equal(STAKE_AMOUNT);
+    });
+
+    it("should not let a proof block cancelling an undersubscribed challenge", async () => {
+      const minId = "cancel-min-test-002";
+      const [minChallengePDA] = getChallengePDA(minId, factoryPDA);
+      const minEscrowVault = await getEscrowVault(minChallengePDA);
 
       await program.methods
-        .joinChallenge(challengeId)
+        .createChallenge(
+          minId,
+          new BN(STAKE_AMOUNT),
+          TOTAL_DAYS,
+          new BN(getFutureTimestamp(2)),
+          challengeParams({ minParticipants: 2 })
+        )
         .accountsPartial({
-          user: winner.publicKey,
+          creator: creator.publicKey,
           factory: factoryPDA,
-          challenge: challengePDA,
-          participant: winnerPDA,
-          userTokenAccount: winnerTokenAccount,
-          escrowVault: escrowVault,
+          challenge: minChallengePDA,
+          tokenMint: usdcMint,
+          escrowVault: minEscrowVault,
           systemProgram: SystemProgram.programId,
           tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         })
-        .signers([winner])
+        .signers([creator])
         .rpc();
 
+      const loneUser = Keypair.generate();
+      await airdrop(loneUser.publicKey);
+      const loneTokenAccount = await setupTokenAccount(loneUser, STAKE_AMOUNT);
+      const [lonePDA] = getParticipantPDA(minChallengePDA, loneUser.publicKey);
+
       await program.methods
-        .joinChallenge(challengeId)
+        .joinChallenge(minId)
         .accountsPartial({
-          user: loser.publicKey,
+          user: loneUser.publicKey,
           factory: factoryPDA,
-          challenge: challengePDA,
-          participant: loserPDA,
-          userTokenAccount: loserTokenAccount,
-          escrowVault: escrowVault,
+          challenge: minChallengePDA,
+          participant: lonePDA,
+          userTokenAccount: loneTokenAccount,
+          escrowVault: minEscrowVault,
           systemProgram: SystemProgram.programId,
           tokenProgram: TOKEN_PROGRAM_ID,
         })
-        .signers([loser])
+        .signers([loneUser])
         .rpc();
 
-      // Wait for start.
       await sleep(2500);
 
-      // Winner submits proof once. For total_days=1, required proofs should be 1 (80% ceil).
+      try {
+        await program.methods
+          .recordProof(minId, 0, proofHash("proof-0"))
+          .accountsPartial({
+            oracle: oracle.publicKey,
+            factory: factoryPDA,
+            challenge: minChallengePDA,
+            participant: lonePDA,
+            proofRecord: getProofRecordPDA(lonePDA, 0)[0],
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([oracle])
+          .rpc();
+        expect.fail("Should have thrown error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("MinParticipantsNotMet");
+      }
+
       await program.methods
-        .recordProof(challengeId)
+        .cancelUndersubscribed(minId)
         .accountsPartial({
-          oracle: oracle.publicKey,
           factory: factoryPDA,
-          challenge: challengePDA,
-          participant: winnerPDA,
+          challenge: minChallengePDA,
         })
-        .signers([oracle])
         .rpc();
 
-      // Ensure the challenge has ended.
-      await sleep((DAY_LENGTH_SECONDS + 2) * 1000);
+      const challenge = await (program.account as any).challengeEscrow.fetch(
+        minChallengePDA
+      );
+      expect(challenge.status.cancelled).to.not.be.undefined;
+    });
 
-      await program.methods
-        .settleChallenge(challengeId)
-        .accountsPartial({
-          oracle: oracle.publicKey,
-          factory: factoryPDA,
-          challenge: challengePDA,
-        })
-        .signers([oracle])
-        .rpc();
+    it("should not let a participant forfeit an undersubscribed challenge", async () => {
+      const minId = "cancel-min-test-003";
+      const [minChallengePDA] = getChallengePDA(minId, factoryPDA);
+      const minEscrowVault = await getEscrowVault(minChallengePDA);
 
-      // Settle both participants, then finalize.
       await program.methods
-        .settleParticipant(challengeId)
+        .createChallenge(
+          minId,
+          new BN(STAKE_AMOUNT),
+          TOTAL_DAYS,
+          new BN(getFutureTimestamp(2)),
+          challengeParams({ minParticipants: 2 })
+        )
         .accountsPartial({
-          oracle: oracle.publicKey,
+          creator: creator.publicKey,
           factory: factoryPDA,
-          challenge: challengePDA,
-          participant: winnerPDA,
+          challenge: minChallengePDA,
+          tokenMint: usdcMint,
+          escrowVault: minEscrowVault,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         })
-        .signers([oracle])
+        .signers([creator])
         .rpc();
 
+      const loneUser = Keypair.generate();
+      await airdrop(loneUser.publicKey);
+      const loneTokenAccount = await setupTokenAccount(loneUser, STAKE_AMOUNT);
+      const [lonePDA] = getParticipantPDA(minChallengePDA, loneUser.publicKey);
+
       await program.methods
-        .settleParticipant(challengeId)
+        .joinChallenge(minId)
         .accountsPartial({
-          oracle: oracle.publicKey,
+          user: loneUser.publicKey,
           factory: factoryPDA,
-          challenge: challengePDA,
-          participant: loserPDA,
+          challenge: minChallengePDA,
+          participant: lonePDA,
+          userTokenAccount: loneTokenAccount,
+          escrowVault: minEscrowVault,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
         })
-        .signers([oracle])
+        .signers([loneUser])
         .rpc();
 
-      await program.methods
-        .finalizeSettlement(challengeId)
+      await sleep(2500);
+
+      // Forfeiting would close the participant and strand the stake once cancelled
+      try {
+        await program.methods
+          .forfeit(minId)
+          .accountsPartial({
+            user: loneUser.publicKey,
+            factory: factoryPDA,
+            challenge: minChallengePDA,
+            participant: lonePDA,
+          })
+          .signers([loneUser])
+          .rpc();
+        expect.fail("Should have thrown error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("MinParticipantsNotMet");
+      }
+
+      await program.methods
+        .cancelUndersubscribed(minId)
         .accountsPartial({
-          oracle: oracle.publicKey,
           factory: factoryPDA,
-          challenge: challengePDA,
+          challenge: minChallengePDA,
         })
-        .signers([oracle])
         .rpc();
 
-      const winnerBalBefore = await getAccount(provider.connection, winnerTokenAccount);
+      const challenge = await (program.account as any).challengeEscrow.fetch(
+        minChallengePDA
+      );
+      expect(challenge.status.cancelled).to.not.be.undefined;
 
       await program.methods
-        .claimPayout(challengeId)
+        .claimRefund(minId)
         .accountsPartial({
-          user: winner.publicKey,
+          user: loneUser.publicKey,
           factory: factoryPDA,
-          challenge: challengePDA,
-          participant: winnerPDA,
-          userTokenAccount: winnerTokenAccount,
-          escrowVault: escrowVault,
+          challenge: minChallengePDA,
+          participant: lonePDA,
+          userTokenAccount: loneTokenAccount,
+          escrowVault: minEscrowVault,
           tokenProgram: TOKEN_PROGRAM_ID,
         })
-        .signers([winner])
+        .signers([loneUser])
         .rpc();
 
-      const winnerBalAfter = await getAccount(provider.connection, winnerTokenAccount);
-      expect(Number(winnerBalAfter.amount) - Number(winnerBalBefore.amount)).to.equal(STAKE_AMOUNT * 2);
-
-      const vaultAccount = await getAccount(provider.connection, escrowVault);
-      expect(Number(vaultAccount.amount)).to.equal(0);
+      const balance = await getAccount(provider.connection, loneTokenAccount);
+      expect(Number(balance.amount)).to.equal(STAKE_AMOUNT);
     });
 
-    it("should allow treasury to claim forfeited stakes when no winners", async () => {
-      const challengeId = "rewards-nowin-001";
-      const [challengePDA] = getChallengePDA(challengeId, factoryPDA);
-      const escrowVault = await getEscrowVault(challengePDA);
+    it("should fail for non-creator to cancel challenge", async () => {
+      // Create a new challenge for this test
+      const newChallengeId = "cancel-auth-test-001";
+      const [newChallengePDA] = getChallengePDA(newChallengeId, factoryPDA);
+      const newEscrowVault = await getEscrowVault(newChallengePDA);
 
-      const u1 = Keypair.generate();
-      const u2 = Keypair.generate();
-      await Promise.all([airdrop(u1.publicKey), airdrop(u2.publicKey)]);
+      await program.methods
+        .createChallenge(
+          newChallengeId,
+          new BN(STAKE_AMOUNT),
+          TOTAL_DAYS,
+          new BN(getFutureTimestamp(300)),
+          challengeParams()
+        )
+        .accountsPartial({
+          creator: creator.publicKey,
+          factory: factoryPDA,
+          challenge: newChallengePDA,
+          tokenMint: usdcMint,
+          escrowVault: newEscrowVault,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        })
+        .signers([creator])
+        .rpc();
 
-      const u1TokenAccount = await setupTokenAccount(u1, STAKE_AMOUNT * 3);
-      const u2TokenAccount = await setupTokenAccount(u2, STAKE_AMOUNT * 3);
+      try {
+        await program.methods
+          .cancelChallenge(newChallengeId)
+          .accountsPartial({
+            creator: user1.publicKey, // Not the creator
+            factory: factoryPDA,
+            challenge: newChallengePDA,
+          })
+          .signers([user1])
+          .rpc();
+        expect.fail("Should have thrown error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("Unauthorized");
+      }
+    });
+  });
 
-      const startTs = getFutureTimestamp(2);
+  describe("Leave Challenge", () => {
+    const challengeId = "leave-test-001";
+    const PENALTY_BPS = 1000; // 10%
+    let challengePDA: PublicKey;
+    let escrowVault: PublicKey;
+    let participantPDA: PublicKey;
+    let leaveUser: Keypair;
+    let leaveUserTokenAccount: PublicKey;
+
+    before(async () => {
+      leaveUser = Keypair.generate();
+      await airdrop(leaveUser.publicKey);
+
+      [challengePDA] = getChallengePDA(challengeId, factoryPDA);
+      escrowVault = await getEscrowVault(challengePDA);
 
       await program.methods
-        .createChallenge(challengeId, new BN(STAKE_AMOUNT), 1, new BN(startTs))
+        .createChallenge(
+          challengeId,
+          new BN(STAKE_AMOUNT),
+          TOTAL_DAYS,
+          new BN(getFutureTimestamp(300)),
+          challengeParams({ earlyExitPenaltyBps: PENALTY_BPS })
+        )
         .accountsPartial({
           creator: creator.publicKey,
           factory: factoryPDA,
@@ -1203,110 +2141,3128 @@ describe("proven-stake", () => {
         .signers([creator])
         .rpc();
 
-      const [p1] = getParticipantPDA(challengePDA, u1.publicKey);
-      const [p2] = getParticipantPDA(challengePDA, u2.publicKey);
+      leaveUserTokenAccount = await setupTokenAccount(leaveUser, STAKE_AMOUNT * 2);
+      [participantPDA] = getParticipantPDA(challengePDA, leaveUser.publicKey);
 
       await program.methods
         .joinChallenge(challengeId)
         .accountsPartial({
-          user: u1.publicKey,
+          user: leaveUser.publicKey,
           factory: factoryPDA,
           challenge: challengePDA,
-          participant: p1,
-          userTokenAccount: u1TokenAccount,
+          participant: participantPDA,
+          userTokenAccount: leaveUserTokenAccount,
           escrowVault: escrowVault,
           systemProgram: SystemProgram.programId,
           tokenProgram: TOKEN_PROGRAM_ID,
         })
-        .signers([u1])
+        .signers([leaveUser])
         .rpc();
+    });
+
+    it("should reject an early-exit penalty above the cap", async () => {
+      const badId = "leave-penalty-cap";
+      const [badChallengePDA] = getChallengePDA(badId, factoryPDA);
+
+      try {
+        await program.methods
+          .createChallenge(
+            badId,
+            new BN(STAKE_AMOUNT),
+            TOTAL_DAYS,
+            new BN(getFutureTimestamp(300)),
+            challengeParams({ earlyExitPenaltyBps: 5001 })
+          )
+          .accountsPartial({
+            creator: creator.publicKey,
+            factory: factoryPDA,
+            challenge: badChallengePDA,
+            tokenMint: usdcMint,
+            escrowVault: await getEscrowVault(badChallengePDA),
+            systemProgram: SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          })
+          .signers([creator])
+          .rpc();
+        expect.fail("Should have thrown error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("PenaltyTooHigh");
+      }
+    });
+
+    it("should refund the stake minus the penalty and close the participant", async () => {
+      const balanceBefore = await getAccount(
+        provider.connection,
+        leaveUserTokenAccount
+      );
 
       await program.methods
-        .joinChallenge(challengeId)
+        .leaveChallenge(challengeId)
         .accountsPartial({
-          user: u2.publicKey,
+          user: leaveUser.publicKey,
           factory: factoryPDA,
           challenge: challengePDA,
-          participant: p2,
-          userTokenAccount: u2TokenAccount,
+          participant: participantPDA,
+          userTokenAccount: leaveUserTokenAccount,
           escrowVault: escrowVault,
-          systemProgram: SystemProgram.programId,
           tokenProgram: TOKEN_PROGRAM_ID,
         })
-        .signers([u2])
+        .signers([leaveUser])
         .rpc();
 
-      // Wait for end without recording proofs, so nobody meets the required days.
-      await sleep((DAY_LENGTH_SECONDS + 3) * 1000);
+      const penalty = (STAKE_AMOUNT * PENALTY_BPS) / 10000;
+      const balanceAfter = await getAccount(
+        provider.connection,
+        leaveUserTokenAccount
+      );
+      expect(Number(balanceAfter.amount) - Number(balanceBefore.amount)).to.equal(
+        STAKE_AMOUNT - penalty
+      );
+
+      const participantInfo = await provider.connection.getAccountInfo(participantPDA);
+      expect(participantInfo).to.be.null;
 
+      const challenge = await (program.account as any).challengeEscrow.fetch(
+        challengePDA
+      );
+      expect(challenge.participantCount).to.equal(0);
+      expect(challenge.activeParticipants).to.equal(0);
+      expect(challenge.penaltyPool.toNumber()).to.equal(penalty);
+    });
+
+    it("should forfeit penalties to the treasury on cancellation", async () => {
       await program.methods
-        .settleChallenge(challengeId)
+        .cancelChallenge(challengeId)
         .accountsPartial({
-          oracle: oracle.publicKey,
+          creator: creator.publicKey,
           factory: factoryPDA,
           challenge: challengePDA,
         })
-        .signers([oracle])
+        .signers([creator])
         .rpc();
 
+      const challenge = await (program.account as any).challengeEscrow.fetch(
+        challengePDA
+      );
+      expect(challenge.forfeitedAmount.toNumber()).to.equal(
+        (STAKE_AMOUNT * PENALTY_BPS) / 10000
+      );
+    });
+
+    it("should let a participant forfeit once started, before any proof", async () => {
+      const forfeitId = "leave-forfeit-001";
+      const [forfeitPDA] = getChallengePDA(forfeitId, factoryPDA);
+      const forfeitVault = await getEscrowVault(forfeitPDA);
+      const forfeitUser = Keypair.generate();
+      await airdrop(forfeitUser.publicKey);
+      const userTokenAccount = await setupTokenAccount(forfeitUser, STAKE_AMOUNT);
+
       await program.methods
-        .settleParticipant(challengeId)
+        .createChallenge(
+          forfeitId,
+          new BN(STAKE_AMOUNT),
+          TOTAL_DAYS,
+          new BN(getFutureTimestamp(2)),
+          challengeParams()
+        )
         .accountsPartial({
-          oracle: oracle.publicKey,
+          creator: creator.publicKey,
           factory: factoryPDA,
-          challenge: challengePDA,
-          participant: p1,
+          challenge: forfeitPDA,
+          tokenMint: usdcMint,
+          escrowVault: forfeitVault,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         })
-        .signers([oracle])
+        .signers([creator])
         .rpc();
 
+      const [forfeitParticipantPDA] = getParticipantPDA(forfeitPDA, forfeitUser.publicKey);
       await program.methods
-        .settleParticipant(challengeId)
+        .joinChallenge(forfeitId)
         .accountsPartial({
-          oracle: oracle.publicKey,
+          user: forfeitUser.publicKey,
+          factory: factoryPDA,
+          challenge: forfeitPDA,
+          participant: forfeitParticipantPDA,
+          userTokenAccount,
+          escrowVault: forfeitVault,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([forfeitUser])
+        .rpc();
+
+      const forfeit = () =>
+        program.methods
+          .forfeit(forfeitId)
+          .accountsPartial({
+            user: forfeitUser.publicKey,
+            factory: factoryPDA,
+            challenge: forfeitPDA,
+            participant: forfeitParticipantPDA,
+          })
+          .signers([forfeitUser])
+          .rpc();
+
+      // Before the start, leave_challenge applies instead
+      try {
+        await forfeit();
+        expect.fail("Should have thrown error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("ChallengeNotStarted");
+      }
+
+      await sleep(2500);
+
+      // No proof has been recorded, so the status is still Created
+      let challenge = await (program.account as any).challengeEscrow.fetch(forfeitPDA);
+      expect(challenge.status.created).to.not.be.undefined;
+
+      await forfeit();
+
+      const participant = await (program.account as any).participant.fetch(
+        forfeitParticipantPDA
+      );
+      expect(participant.forfeited).to.be.true;
+      challenge = await (program.account as any).challengeEscrow.fetch(forfeitPDA);
+      expect(challenge.loserCount).to.equal(1);
+    });
+  });
+
+  describe("Pause and Resume", () => {
+    const challengeId = "pause-test-001";
+    let challengePDA: PublicKey;
+    let escrowVault: PublicKey;
+    let participantPDA: PublicKey;
+    let pauseUser: Keypair;
+
+    const pauseChallenge = (signer: Keypair) =>
+      program.methods
+        .pauseChallenge(challengeId)
+        .accountsPartial({
+          signer: signer.publicKey,
           factory: factoryPDA,
           challenge: challengePDA,
-          participant: p2,
         })
-        .signers([oracle])
+        .signers([signer])
         .rpc();
 
-      await program.methods
-        .finalizeSettlement(challengeId)
+    const resumeChallenge = (signer: Keypair) =>
+      program.methods
+        .resumeChallenge(challengeId)
+        .accountsPartial({
+          signer: signer.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+        })
+        .signers([signer])
+        .rpc();
+
+    const recordProof = () =>
+      program.methods
+        .recordProof(challengeId, 0, proofHash("pause-0"))
         .accountsPartial({
           oracle: oracle.publicKey,
           factory: factoryPDA,
           challenge: challengePDA,
+          participant: participantPDA,
+          proofRecord: getProofRecordPDA(participantPDA, 0)[0],
+          systemProgram: SystemProgram.programId,
         })
         .signers([oracle])
         .rpc();
 
-      const treasuryTokenAccount = await createAssociatedTokenAccount(
-        provider.connection,
-        treasury,
-        usdcMint,
-        treasury.publicKey
-      );
+    before(async () => {
+      pauseUser = Keypair.generate();
+      await airdrop(pauseUser.publicKey);
 
-      const treasuryBalBefore = await getAccount(provider.connection, treasuryTokenAccount);
+      [challengePDA] = getChallengePDA(challengeId, factoryPDA);
+      escrowVault = await getEscrowVault(challengePDA);
 
       await program.methods
-        .claimForfeitedStakes(challengeId)
+        .createChallenge(
+          challengeId,
+          new BN(STAKE_AMOUNT),
+          TOTAL_DAYS,
+          new BN(getFutureTimestamp(3)),
+          challengeParams()
+        )
         .accountsPartial({
-          treasury: treasury.publicKey,
+          creator: creator.publicKey,
           factory: factoryPDA,
           challenge: challengePDA,
-          treasuryTokenAccount: treasuryTokenAccount,
+          tokenMint: usdcMint,
           escrowVault: escrowVault,
+          systemProgram: SystemProgram.programId,
           tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         })
-        .signers([treasury])
+        .signers([creator])
         .rpc();
 
-      const treasuryBalAfter = await getAccount(provider.connection, treasuryTokenAccount);
-      expect(Number(treasuryBalAfter.amount) - Number(treasuryBalBefore.amount)).to.equal(
-        STAKE_AMOUNT * 2
-      );
+      [participantPDA] = getParticipantPDA(challengePDA, pauseUser.publicKey);
+      await program.methods
+        .joinChallenge(challengeId)
+        .accountsPartial({
+          user: pauseUser.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+          participant: participantPDA,
+          userTokenAccount: await setupTokenAccount(pauseUser, STAKE_AMOUNT),
+          escrowVault: escrowVault,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([pauseUser])
+        .rpc();
+
+      await sleep(4000);
+    });
+
+    it("should only let the authority or oracle pause", async () => {
+      try {
+        await pauseChallenge(creator);
+        expect.fail("Should have thrown error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("Unauthorized");
+      }
+    });
+
+    it("should block proofs and joins while paused", async () => {
+      await pauseChallenge(oracle);
+
+      const challenge = await (program.account as any).challengeEscrow.fetch(
+        challengePDA
+      );
+      expect(challenge.status.paused).to.not.be.undefined;
+
+      try {
+        await recordProof();
+        expect.fail("Should have thrown error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("ChallengePaused");
+      }
+
+      const lateUser = Keypair.generate();
+      await airdrop(lateUser.publicKey);
+      try {
+        await program.methods
+          .joinChallenge(challengeId)
+          .accountsPartial({
+            user: lateUser.publicKey,
+            factory: factoryPDA,
+            challenge: challengePDA,
+            participant: getParticipantPDA(challengePDA, lateUser.publicKey)[0],
+            userTokenAccount: await setupTokenAccount(lateUser, STAKE_AMOUNT),
+            escrowVault: escrowVault,
+            systemProgram: SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([lateUser])
+          .rpc();
+        expect.fail("Should have thrown error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("ChallengePaused");
+      }
+    });
+
+    it("should extend the challenge by the paused duration on resume", async () => {
+      const before = await (program.account as any).challengeEscrow.fetch(
+        challengePDA
+      );
+      await sleep(2000);
+      await resumeChallenge(authority);
+
+      const after = await (program.account as any).challengeEscrow.fetch(
+        challengePDA
+      );
+      expect(after.status.created).to.not.be.undefined;
+      const extension = after.endTs.toNumber() - before.endTs.toNumber();
+      expect(extension).to.be.at.least(2);
+      expect(after.startTs.toNumber() - before.startTs.toNumber()).to.equal(extension);
+
+      await recordProof();
+      const participant = await (program.account as any).participant.fetch(
+        participantPDA
+      );
+      expect(participant.proofDays).to.equal(1);
+    });
+  });
+
+  // ============================================================
+  // REWARDS TESTS (END-TO-END PAYOUTS)
+  // ============================================================
+
+  describe("Rewards (Payouts + Forfeits)", () => {
+    const DAY_LENGTH_SECONDS = 3;
+
+    before(async () => {
+      // Shorten "day length" and drop the dispute window so challenges can
+      // end and settle quickly on localnet.
+      await applyFactoryUpdate(DAY_LENGTH_SECONDS, 0);
+    });
+
+    after(async () => {
+      // Restore default day length and dispute window (86400) so future local
+      // runs behave normally.
+      await applyFactoryUpdate(24 * 60 * 60, 24 * 60 * 60);
+    });
+
+    it("should only cancel undersubscribed challenges, never settle them", async () => {
+      const setup = async (challengeId: string, payoutMode: object) => {
+        const [challengePDA] = getChallengePDA(challengeId, factoryPDA);
+        const escrowVault = await getEscrowVault(challengePDA);
+        const user = Keypair.generate();
+        await airdrop(user.publicKey);
+        const userTokenAccount = await setupTokenAccount(user, STAKE_AMOUNT);
+        const [participantPDA] = getParticipantPDA(challengePDA, user.publicKey);
+
+        await program.methods
+          .createChallenge(
+            challengeId,
+            new BN(STAKE_AMOUNT),
+            1,
+            new BN(getFutureTimestamp(2)),
+            challengeParams({ payoutMode, minParticipants: 2 })
+          )
+          .accountsPartial({
+            creator: creator.publicKey,
+            factory: factoryPDA,
+            challenge: challengePDA,
+            tokenMint: usdcMint,
+            escrowVault: escrowVault,
+            systemProgram: SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          })
+          .signers([creator])
+          .rpc();
+        await program.methods
+          .joinChallenge(challengeId)
+          .accountsPartial({
+            user: user.publicKey,
+            factory: factoryPDA,
+            challenge: challengePDA,
+            participant: participantPDA,
+            userTokenAccount,
+            escrowVault: escrowVault,
+            systemProgram: SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([user])
+          .rpc();
+        return { challengeId, challengePDA, escrowVault, user, userTokenAccount, participantPDA };
+      };
+
+      const threshold = await setup("rewards-undersubscribed-001", { threshold: {} });
+      const daily = await setup("rewards-undersubscribed-002", { daily: {} });
+
+      // Wait for the single day to end
+      await sleep((DAY_LENGTH_SECONDS + 3) * 1000);
+
+      const expectUndersubscribed = async (call: Promise<string>) => {
+        try {
+          await call;
+          expect.fail("Should have thrown error");
+        } catch (err: any) {
+          expect(err.toString()).to.include("MinParticipantsNotMet");
+        }
+      };
+
+      await expectUndersubscribed(
+        program.methods
+          .settleChallenge(threshold.challengeId)
+          .accountsPartial({
+            oracle: oracle.publicKey,
+            factory: factoryPDA,
+            challenge: threshold.challengePDA,
+          })
+          .signers([oracle])
+          .rpc()
+      );
+      await expectUndersubscribed(
+        program.methods
+          .settleParticipant(threshold.challengeId)
+          .accountsPartial({
+            oracle: oracle.publicKey,
+            factory: factoryPDA,
+            challenge: threshold.challengePDA,
+            participant: threshold.participantPDA,
+          })
+          .signers([oracle])
+          .rpc()
+      );
+      await expectUndersubscribed(
+        program.methods
+          .settleDay(daily.challengeId, 0)
+          .accountsPartial({
+            oracle: oracle.publicKey,
+            factory: factoryPDA,
+            challenge: daily.challengePDA,
+            dailySettlement: getDailySettlementPDA(daily.challengePDA, 0)[0],
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([oracle])
+          .rpc()
+      );
+
+      // Both are cancelled instead, and every stake comes back in full
+      for (const c of [threshold, daily]) {
+        await program.methods
+          .cancelUndersubscribed(c.challengeId)
+          .accountsPartial({
+            factory: factoryPDA,
+            challenge: c.challengePDA,
+          })
+          .rpc();
+        await program.methods
+          .claimRefund(c.challengeId)
+          .accountsPartial({
+            user: c.user.publicKey,
+            factory: factoryPDA,
+            challenge: c.challengePDA,
+            participant: c.participantPDA,
+            userTokenAccount: c.userTokenAccount,
+            escrowVault: c.escrowVault,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([c.user])
+          .rpc();
+
+        const balance = await getAccount(provider.connection, c.userTokenAccount);
+        expect(Number(balance.amount)).to.equal(STAKE_AMOUNT);
+        const vaultAccount = await getAccount(provider.connection, c.escrowVault);
+        expect(Number(vaultAccount.amount)).to.equal(0);
+      }
+    });
+
+    it("should pay a winner stake + bonus (mixed winners/losers)", async () => {
+      const challengeId = "rewards-mixed-001";
+      const [challengePDA] = getChallengePDA(challengeId, factoryPDA);
+      const escrowVault = await getEscrowVault(challengePDA);
+
+      const winner = Keypair.generate();
+      const loser = Keypair.generate();
+      await Promise.all([airdrop(winner.publicKey), airdrop(loser.publicKey)]);
+
+      const winnerTokenAccount = await setupTokenAccount(winner, STAKE_AMOUNT * 3);
+      const loserTokenAccount = await setupTokenAccount(loser, STAKE_AMOUNT * 3);
+
+      const startTs = getFutureTimestamp(2);
+
+      await program.methods
+        .createChallenge(
+          challengeId,
+          new BN(STAKE_AMOUNT),
+          1,
+          new BN(startTs),
+          challengeParams()
+        )
+        .accountsPartial({
+          creator: creator.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+          tokenMint: usdcMint,
+          escrowVault: escrowVault,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        })
+        .signers([creator])
+        .rpc();
+
+      const [winnerPDA] = getParticipantPDA(challengePDA, winner.publicKey);
+      const [loserPDA] = getParticipantPDA(challengePDA, loser.publicKey);
+
+      await program.methods
+        .joinChallenge(challengeId)
+        .accountsPartial({
+          user: winner.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+          participant: winnerPDA,
+          userTokenAccount: winnerTokenAccount,
+          escrowVault: escrowVault,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([winner])
+        .rpc();
+
+      await program.methods
+        .joinChallenge(challengeId)
+        .accountsPartial({
+          user: loser.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+          participant: loserPDA,
+          userTokenAccount: loserTokenAccount,
+          escrowVault: escrowVault,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([loser])
+        .rpc();
+
+      // Wait for start.
+      await sleep(2500);
+
+      // Winner submits proof once. For total_days=1, required proofs should be 1 (80% ceil).
+      await program.methods
+        .recordProof(challengeId, 0, proofHash("proof-0"))
+        .accountsPartial({
+          oracle: oracle.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+          participant: winnerPDA,
+          proofRecord: getProofRecordPDA(winnerPDA, 0)[0],
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([oracle])
+        .rpc();
+
+      // Ensure the challenge has ended.
+      await sleep((DAY_LENGTH_SECONDS + 2) * 1000);
+
+      await program.methods
+        .settleChallenge(challengeId)
+        .accountsPartial({
+          oracle: oracle.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+        })
+        .signers([oracle])
+        .rpc();
+
+      // Settle both participants, then finalize.
+      await program.methods
+        .settleParticipant(challengeId)
+        .accountsPartial({
+          oracle: oracle.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+          participant: winnerPDA,
+        })
+        .signers([oracle])
+        .rpc();
+
+      await program.methods
+        .settleParticipant(challengeId)
+        .accountsPartial({
+          oracle: oracle.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+          participant: loserPDA,
+        })
+        .signers([oracle])
+        .rpc();
+
+      await program.methods
+        .finalizeSettlement(challengeId)
+        .accountsPartial({
+          oracle: oracle.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+        })
+        .signers([oracle])
+        .rpc();
+
+      // Claims wait for the entitlement to be recorded
+      try {
+        await program.methods
+          .claimPayout(challengeId)
+          .accountsPartial({
+            user: winner.publicKey,
+            factory: factoryPDA,
+            challenge: challengePDA,
+            participant: winnerPDA,
+            userTokenAccount: winnerTokenAccount,
+            escrowVault: escrowVault,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([winner])
+          .rpc();
+        expect.fail("Should have thrown error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("PayoutNotAssigned");
+      }
+
+      await assignPayouts(challengeId, challengePDA, [winnerPDA, loserPDA]);
+
+      const winnerParticipant = await (program.account as any).participant.fetch(winnerPDA);
+      const loserParticipant = await (program.account as any).participant.fetch(loserPDA);
+      expect(winnerParticipant.payoutAmount.toNumber()).to.equal(STAKE_AMOUNT * 2);
+      expect(loserParticipant.payoutAmount.toNumber()).to.equal(0);
+
+      // Each participant is assigned once
+      try {
+        await assignPayouts(challengeId, challengePDA, [winnerPDA]);
+        expect.fail("Should have thrown error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("PayoutAlreadyAssigned");
+      }
+
+      const winnerBalBefore = await getAccount(provider.connection, winnerTokenAccount);
+
+      await program.methods
+        .claimPayout(challengeId)
+        .accountsPartial({
+          user: winner.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+          participant: winnerPDA,
+          userTokenAccount: winnerTokenAccount,
+          escrowVault: escrowVault,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([winner])
+        .rpc();
+
+      const winnerBalAfter = await getAccount(provider.connection, winnerTokenAccount);
+      expect(Number(winnerBalAfter.amount) - Number(winnerBalBefore.amount)).to.equal(STAKE_AMOUNT * 2);
+
+      const vaultAccount = await getAccount(provider.connection, escrowVault);
+      expect(Number(vaultAccount.amount)).to.equal(0);
+    });
+
+    it("should assign remainder dust by settlement order, not claim order", async () => {
+      const challengeId = "rewards-remainder-001";
+      const [challengePDA] = getChallengePDA(challengeId, factoryPDA);
+      const escrowVault = await getEscrowVault(challengePDA);
+
+      const winners = [Keypair.generate(), Keypair.generate(), Keypair.generate()];
+      const loser = Keypair.generate();
+      await Promise.all([...winners, loser].map((u) => airdrop(u.publicKey)));
+
+      const users = [...winners, loser];
+      const tokenAccounts: PublicKey[] = [];
+      for (const user of users) {
+        tokenAccounts.push(await setupTokenAccount(user, STAKE_AMOUNT));
+      }
+      const participants = users.map((u) => getParticipantPDA(challengePDA, u.publicKey)[0]);
+
+      await program.methods
+        .createChallenge(
+          challengeId,
+          new BN(STAKE_AMOUNT),
+          1,
+          new BN(getFutureTimestamp(2)),
+          challengeParams()
+        )
+        .accountsPartial({
+          creator: creator.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+          tokenMint: usdcMint,
+          escrowVault: escrowVault,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        })
+        .signers([creator])
+        .rpc();
+
+      for (let i = 0; i < users.length; i++) {
+        await program.methods
+          .joinChallenge(challengeId)
+          .accountsPartial({
+            user: users[i].publicKey,
+            factory: factoryPDA,
+            challenge: challengePDA,
+            participant: participants[i],
+            userTokenAccount: tokenAccounts[i],
+            escrowVault: escrowVault,
+            systemProgram: SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([users[i]])
+          .rpc();
+      }
+
+      await sleep(2500);
+
+      for (const participant of participants.slice(0, 3)) {
+        await program.methods
+          .recordProof(challengeId, 0, proofHash("proof-0"))
+          .accountsPartial({
+            oracle: oracle.publicKey,
+            factory: factoryPDA,
+            challenge: challengePDA,
+            participant,
+            proofRecord: getProofRecordPDA(participant, 0)[0],
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([oracle])
+          .rpc();
+      }
+
+      await sleep((DAY_LENGTH_SECONDS + 2) * 1000);
+
+      await program.methods
+        .settleChallenge(challengeId)
+        .accountsPartial({
+          oracle: oracle.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+        })
+        .signers([oracle])
+        .rpc();
+
+      for (const participant of participants) {
+        await program.methods
+          .settleParticipant(challengeId)
+          .accountsPartial({
+            oracle: oracle.publicKey,
+            factory: factoryPDA,
+            challenge: challengePDA,
+            participant,
+          })
+          .signers([oracle])
+          .rpc();
+      }
+
+      await program.methods
+        .finalizeSettlement(challengeId)
+        .accountsPartial({
+          oracle: oracle.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+        })
+        .signers([oracle])
+        .rpc();
+
+      await assignPayouts(challengeId, challengePDA, participants);
+
+      const challenge = await (program.account as any).challengeEscrow.fetch(
+        challengePDA
+      );
+      const bonus = Math.floor(STAKE_AMOUNT / 3);
+      expect(challenge.bonusPerWinner.toNumber()).to.equal(bonus);
+      expect(challenge.remainder.toNumber()).to.equal(STAKE_AMOUNT % 3);
+
+      // Claim in reverse settlement order
+      for (let i = 2; i >= 0; i--) {
+        await program.methods
+          .claimPayout(challengeId)
+          .accountsPartial({
+            user: winners[i].publicKey,
+            factory: factoryPDA,
+            challenge: challengePDA,
+            participant: participants[i],
+            userTokenAccount: tokenAccounts[i],
+            escrowVault: escrowVault,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([winners[i]])
+          .rpc();
+      }
+
+      // The dust falls on the last-settled winner's slice
+      const balances = await Promise.all(
+        tokenAccounts
+          .slice(0, 3)
+          .map(async (a) => Number((await getAccount(provider.connection, a)).amount))
+      );
+      expect(balances).to.deep.equal([
+        STAKE_AMOUNT + bonus,
+        STAKE_AMOUNT + bonus,
+        STAKE_AMOUNT + bonus + (STAKE_AMOUNT % 3),
+      ]);
+
+      const vaultAccount = await getAccount(provider.connection, escrowVault);
+      expect(Number(vaultAccount.amount)).to.equal(0);
+    });
+
+    it("should let anyone distribute a payout, creating the winner's token account", async () => {
+      const challengeId = "rewards-distribute-001";
+      const [challengePDA] = getChallengePDA(challengeId, factoryPDA);
+      const escrowVault = await getEscrowVault(challengePDA);
+
+      const winner = Keypair.generate();
+      await airdrop(winner.publicKey);
+      const winnerTokenAccount = await setupTokenAccount(winner, STAKE_AMOUNT);
+      const [winnerPDA] = getParticipantPDA(challengePDA, winner.publicKey);
+
+      await program.methods
+        .createChallenge(
+          challengeId,
+          new BN(STAKE_AMOUNT),
+          1,
+          new BN(getFutureTimestamp(2)),
+          challengeParams()
+        )
+        .accountsPartial({
+          creator: creator.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+          tokenMint: usdcMint,
+          escrowVault: escrowVault,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        })
+        .signers([creator])
+        .rpc();
+
+      await program.methods
+        .joinChallenge(challengeId)
+        .accountsPartial({
+          user: winner.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+          participant: winnerPDA,
+          userTokenAccount: winnerTokenAccount,
+          escrowVault: escrowVault,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([winner])
+        .rpc();
+
+      // The winner closes their now-empty token account
+      await closeAccount(
+        provider.connection,
+        winner,
+        winnerTokenAccount,
+        winner.publicKey,
+        winner
+      );
+
+      await sleep(2500);
+
+      await program.methods
+        .recordProof(challengeId, 0, proofHash("proof-0"))
+        .accountsPartial({
+          oracle: oracle.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+          participant: winnerPDA,
+          proofRecord: getProofRecordPDA(winnerPDA, 0)[0],
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([oracle])
+        .rpc();
+
+      await sleep((DAY_LENGTH_SECONDS + 2) * 1000);
+
+      await program.methods
+        .settleChallenge(challengeId)
+        .accountsPartial({
+          oracle: oracle.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+        })
+        .signers([oracle])
+        .rpc();
+
+      await program.methods
+        .settleParticipant(challengeId)
+        .accountsPartial({
+          oracle: oracle.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+          participant: winnerPDA,
+        })
+        .signers([oracle])
+        .rpc();
+
+      await program.methods
+        .finalizeSettlement(challengeId)
+        .accountsPartial({
+          oracle: oracle.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+        })
+        .signers([oracle])
+        .rpc();
+
+      await assignPayouts(challengeId, challengePDA, [winnerPDA]);
+
+      expect(await provider.connection.getAccountInfo(winnerTokenAccount)).to.be.null;
+
+      // Pushed by a third party, who pays for the new token account
+      await program.methods
+        .distributePayout(challengeId)
+        .accountsPartial({
+          payer: provider.wallet.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+          participant: winnerPDA,
+          user: winner.publicKey,
+          tokenMint: usdcMint,
+          userTokenAccount: winnerTokenAccount,
+          escrowVault: escrowVault,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+
+      const balance = await getAccount(provider.connection, winnerTokenAccount);
+      expect(Number(balance.amount)).to.equal(STAKE_AMOUNT);
+
+      const participant = await (program.account as any).participant.fetch(winnerPDA);
+      expect(participant.payoutClaimed).to.be.true;
+
+      const vaultAccount = await getAccount(provider.connection, escrowVault);
+      expect(Number(vaultAccount.amount)).to.equal(0);
+    });
+
+    it("should allow treasury to claim forfeited stakes when no winners", async () => {
+      const challengeId = "rewards-nowin-001";
+      const [challengePDA] = getChallengePDA(challengeId, factoryPDA);
+      const escrowVault = await getEscrowVault(challengePDA);
+
+      const u1 = Keypair.generate();
+      const u2 = Keypair.generate();
+      await Promise.all([airdrop(u1.publicKey), airdrop(u2.publicKey)]);
+
+      const u1TokenAccount = await setupTokenAccount(u1, STAKE_AMOUNT * 3);
+      const u2TokenAccount = await setupTokenAccount(u2, STAKE_AMOUNT * 3);
+
+      const startTs = getFutureTimestamp(2);
+
+      await program.methods
+        .createChallenge(
+          challengeId,
+          new BN(STAKE_AMOUNT),
+          1,
+          new BN(startTs),
+          challengeParams()
+        )
+        .accountsPartial({
+          creator: creator.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+          tokenMint: usdcMint,
+          escrowVault: escrowVault,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        })
+        .signers([creator])
+        .rpc();
+
+      const [p1] = getParticipantPDA(challengePDA, u1.publicKey);
+      const [p2] = getParticipantPDA(challengePDA, u2.publicKey);
+
+      await program.methods
+        .joinChallenge(challengeId)
+        .accountsPartial({
+          user: u1.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+          participant: p1,
+          userTokenAccount: u1TokenAccount,
+          escrowVault: escrowVault,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([u1])
+        .rpc();
+
+      await program.methods
+        .joinChallenge(challengeId)
+        .accountsPartial({
+          user: u2.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+          participant: p2,
+          userTokenAccount: u2TokenAccount,
+          escrowVault: escrowVault,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([u2])
+        .rpc();
+
+      // Wait for end without recording proofs, so nobody meets the required days.
+      await sleep((DAY_LENGTH_SECONDS + 3) * 1000);
+
+      await program.methods
+        .settleChallenge(challengeId)
+        .accountsPartial({
+          oracle: oracle.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+        })
+        .signers([oracle])
+        .rpc();
+
+      await program.methods
+        .settleParticipant(challengeId)
+        .accountsPartial({
+          oracle: oracle.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+          participant: p1,
+        })
+        .signers([oracle])
+        .rpc();
+
+      await program.methods
+        .settleParticipant(challengeId)
+        .accountsPartial({
+          oracle: oracle.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+          participant: p2,
+        })
+        .signers([oracle])
+        .rpc();
+
+      await program.methods
+        .finalizeSettlement(challengeId)
+        .accountsPartial({
+          oracle: oracle.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+        })
+        .signers([oracle])
+        .rpc();
+
+      const treasuryTokenAccount = await createAssociatedTokenAccount(
+        provider.connection,
+        treasury,
+        usdcMint,
+        treasury.publicKey
+      );
+
+      const treasuryBalBefore = await getAccount(provider.connection, treasuryTokenAccount);
+
+      await program.methods
+        .claimForfeitedStakes(challengeId)
+        .accountsPartial({
+          treasury: treasury.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+          treasuryTokenAccount: treasuryTokenAccount,
+          escrowVault: escrowVault,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([treasury])
+        .rpc();
+
+      const treasuryBalAfter = await getAccount(provider.connection, treasuryTokenAccount);
+      expect(Number(treasuryBalAfter.amount) - Number(treasuryBalBefore.amount)).to.equal(
+        STAKE_AMOUNT * 2
+      );
+    });
+
+    it("should take the platform fee from losers' stakes", async () => {
+      const FEE_BPS = 1000; // 10%
+
+      try {
+        await program.methods
+          .updateFactory(null, null, null, null, null, 1001, null, null)
+          .accountsPartial({
+            authority: authority.publicKey,
+            factory: factoryPDA,
+          })
+          .signers([authority])
+          .rpc();
+        expect.fail("Should have thrown error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("FeeTooHigh");
+      }
+
+      await applyFactoryUpdate(null, null, FEE_BPS);
+
+      const { challengeId, challengePDA, escrowVault, users, claimPayout } =
+        await runSettledChallenge(
+          {
+            challengeId: "rewards-fee-001",
+            // The challenge keeps the fee it was created with
+            afterCreate: () => applyFactoryUpdate(null, null, 0),
+          },
+          [[0], []]
+        );
+      const [winner] = users;
+
+      const fee = (STAKE_AMOUNT * FEE_BPS) / 10000;
+      const challenge = await (program.account as any).challengeEscrow.fetch(
+        challengePDA
+      );
+      expect(challenge.feeAmount.toNumber()).to.equal(fee);
+      expect(challenge.bonusPerWinner.toNumber()).to.equal(STAKE_AMOUNT - fee);
+
+      await claimPayout(winner);
+
+      const winnerBal = await getAccount(provider.connection, winner.tokenAccount);
+      expect(Number(winnerBal.amount)).to.equal(STAKE_AMOUNT * 2 - fee);
+
+      // The vault can't be closed while the fee is unclaimed
+      try {
+        await program.methods
+          .closeEscrowVault(challengeId)
+          .accountsPartial({
+            creator: creator.publicKey,
+            factory: factoryPDA,
+            challenge: challengePDA,
+            escrowVault: escrowVault,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([creator])
+          .rpc();
+        expect.fail("Should have thrown error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("PlatformFeeUnclaimed");
+      }
+
+      const treasuryTokenAccount = await anchor.utils.token.associatedAddress({
+        mint: usdcMint,
+        owner: treasury.publicKey,
+      });
+      const treasuryBalBefore = await getAccount(provider.connection, treasuryTokenAccount);
+
+      await program.methods
+        .claimPlatformFee(challengeId)
+        .accountsPartial({
+          treasury: treasury.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+          treasuryTokenAccount: treasuryTokenAccount,
+          escrowVault: escrowVault,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([treasury])
+        .rpc();
+
+      const treasuryBalAfter = await getAccount(provider.connection, treasuryTokenAccount);
+      expect(Number(treasuryBalAfter.amount) - Number(treasuryBalBefore.amount)).to.equal(fee);
+
+      const vaultAccount = await getAccount(provider.connection, escrowVault);
+      expect(Number(vaultAccount.amount)).to.equal(0);
+    });
+
+    it("should take a capped creator fee from losers' stakes", async () => {
+      const CREATOR_FEE_BPS = 500; // 5%, the factory's default cap
+
+      try {
+        await createTestChallenge("rewards-creator-fee-001", 1, {
+          creatorFeeBps: CREATOR_FEE_BPS + 1,
+        });
+        expect.fail("Should have thrown error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("FeeTooHigh");
+      }
+
+      const { challengeId, challengePDA, escrowVault, users, claimPayout } =
+        await runSettledChallenge(
+          {
+            challengeId: "rewards-creator-fee-001",
+            overrides: { creatorFeeBps: CREATOR_FEE_BPS },
+          },
+          [[0], []]
+        );
+      const [winner] = users;
+
+      const creatorFee = (STAKE_AMOUNT * CREATOR_FEE_BPS) / 10000;
+      const challenge = await (program.account as any).challengeEscrow.fetch(
+        challengePDA
+      );
+      expect(challenge.creatorFeeAmount.toNumber()).to.equal(creatorFee);
+      expect(challenge.bonusPerWinner.toNumber()).to.equal(STAKE_AMOUNT - creatorFee);
+
+      await claimPayout(winner);
+
+      const winnerBal = await getAccount(provider.connection, winner.tokenAccount);
+      expect(Number(winnerBal.amount)).to.equal(STAKE_AMOUNT * 2 - creatorFee);
+
+      // The vault can't be closed while the creator fee is unclaimed
+      try {
+        await program.methods
+          .closeEscrowVault(challengeId)
+          .accountsPartial({
+            creator: creator.publicKey,
+            factory: factoryPDA,
+            challenge: challengePDA,
+            escrowVault: escrowVault,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([creator])
+          .rpc();
+        expect.fail("Should have thrown error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("CreatorFeeUnclaimed");
+      }
+
+      const creatorTokenAccount = await setupTokenAccount(creator, 0);
+      await program.methods
+        .claimCreatorFee(challengeId)
+        .accountsPartial({
+          creator: creator.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+          creatorTokenAccount,
+          escrowVault: escrowVault,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([creator])
+        .rpc();
+
+      const creatorBal = await getAccount(provider.connection, creatorTokenAccount);
+      expect(Number(creatorBal.amount)).to.equal(creatorFee);
+
+      const vaultAccount = await getAccount(provider.connection, escrowVault);
+      expect(Number(vaultAccount.amount)).to.equal(0);
+    });
+
+    it("should add the sponsor pool to winners' bonus", async () => {
+      const SPONSOR_AMOUNT = STAKE_AMOUNT / 2;
+
+      const sponsor = Keypair.generate();
+      await airdrop(sponsor.publicKey);
+      const sponsorTokenAccount = await setupTokenAccount(sponsor, SPONSOR_AMOUNT);
+
+      const challengeId = "rewards-sponsor-001";
+      const [challengePDA] = getChallengePDA(challengeId, factoryPDA);
+      const escrowVault = await getEscrowVault(challengePDA);
+      const [sponsorDepositPDA] = getSponsorDepositPDA(challengePDA, sponsor.publicKey);
+
+      const { users, claimPayout } = await runSettledChallenge(
+        {
+          challengeId,
+          afterCreate: async () => {
+            // Two deposits from the same sponsor accumulate on one record
+            for (const amount of [SPONSOR_AMOUNT / 2, SPONSOR_AMOUNT / 2]) {
+              await program.methods
+                .fundPrizePool(challengeId, new BN(amount))
+                .accountsPartial({
+                  sponsor: sponsor.publicKey,
+                  factory: factoryPDA,
+                  challenge: challengePDA,
+                  sponsorDeposit: sponsorDepositPDA,
+                  sponsorTokenAccount: sponsorTokenAccount,
+                  escrowVault: escrowVault,
+                  systemProgram: SystemProgram.programId,
+                  tokenProgram: TOKEN_PROGRAM_ID,
+                })
+                .signers([sponsor])
+                .rpc();
+            }
+
+            const deposit = await (program.account as any).sponsorDeposit.fetch(
+              sponsorDepositPDA
+            );
+            expect(deposit.amount.toNumber()).to.equal(SPONSOR_AMOUNT);
+          },
+        },
+        [[0], []]
+      );
+      const [winner] = users;
+
+      await claimPayout(winner);
+
+      const winnerBal = await getAccount(provider.connection, winner.tokenAccount);
+      expect(Number(winnerBal.amount)).to.equal(STAKE_AMOUNT * 2 + SPONSOR_AMOUNT);
+
+      // The pool went to the winner, so closing the deposit refunds nothing
+      await program.methods
+        .closeSponsorDeposit(challengeId)
+        .accountsPartial({
+          sponsor: sponsor.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+          sponsorDeposit: sponsorDepositPDA,
+          sponsorTokenAccount: sponsorTokenAccount,
+          escrowVault: escrowVault,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([sponsor])
+        .rpc();
+
+      const sponsorBal = await getAccount(provider.connection, sponsorTokenAccount);
+      expect(Number(sponsorBal.amount)).to.equal(0);
+
+      const vaultAccount = await getAccount(provider.connection, escrowVault);
+      expect(Number(vaultAccount.amount)).to.equal(0);
+    });
+
+    it("should pay out in proportion to proof days (proportional mode)", async () => {
+      // Steady proves both days, partial only the first (below the 80% threshold).
+      const { challengePDA, escrowVault, users, claimPayout } = await runSettledChallenge(
+        {
+          challengeId: "rewards-proportional-001",
+          totalDays: 2,
+          overrides: { payoutMode: { proportional: {} } },
+        },
+        [[0, 1], [0], []]
+      );
+      const [steady, partial] = users;
+
+      // Missed stake: half of partial's plus all of absent's, shared 2:1 by proof days
+      const challenge = await (program.account as any).challengeEscrow.fetch(
+        challengePDA
+      );
+      expect(challenge.winnerCount).to.equal(2);
+      expect(challenge.loserCount).to.equal(1);
+      expect(challenge.bonusPool.toNumber()).to.equal((STAKE_AMOUNT * 3) / 2);
+
+      await claimPayout(steady);
+      await claimPayout(partial);
+
+      const steadyBal = await getAccount(provider.connection, steady.tokenAccount);
+      const partialBal = await getAccount(provider.connection, partial.tokenAccount);
+      expect(Number(steadyBal.amount)).to.equal(STAKE_AMOUNT * 2);
+      expect(Number(partialBal.amount)).to.equal(STAKE_AMOUNT);
+
+      const vaultAccount = await getAccount(provider.connection, escrowVault);
+      expect(Number(vaultAccount.amount)).to.equal(0);
+    });
+
+    it("should weight winners' bonus by reward bracket", async () => {
+      const challengeId = "rewards-brackets-001";
+
+      // The lowest bracket must start at the win threshold
+      try {
+        await createTestChallenge(challengeId, 2, {
+          thresholdBps: 5000,
+          rewardBrackets: [{ thresholdBps: 9000, weight: 1 }],
+        });
+        expect.fail("Should have thrown error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("InvalidRewardBrackets");
+      }
+
+      // Perfect attendance earns three times the bonus of a passing winner
+      const { escrowVault, users, claimPayout } = await runSettledChallenge(
+        {
+          challengeId,
+          totalDays: 2,
+          overrides: {
+            thresholdBps: 5000,
+            rewardBrackets: [
+              { thresholdBps: 5000, weight: 1 },
+              { thresholdBps: 10000, weight: 3 },
+            ],
+          },
+        },
+        [[0, 1], [0], []]
+      );
+      const [perfect, passing] = users;
+
+      const perfectParticipant = await (program.account as any).participant.fetch(
+        perfect.participant
+      );
+      expect(perfectParticipant.bracket).to.equal(1);
+
+      await claimPayout(perfect);
+      await claimPayout(passing);
+
+      const perfectBal = await getAccount(provider.connection, perfect.tokenAccount);
+      const passingBal = await getAccount(provider.connection, passing.tokenAccount);
+      expect(Number(perfectBal.amount)).to.equal(STAKE_AMOUNT + (STAKE_AMOUNT * 3) / 4);
+      expect(Number(passingBal.amount)).to.equal(STAKE_AMOUNT + STAKE_AMOUNT / 4);
+
+      const vaultAccount = await getAccount(provider.connection, escrowVault);
+      expect(Number(vaultAccount.amount)).to.equal(0);
+    });
+
+    it("should prorate a late joiner's stake and required days", async () => {
+      // The late joiner stakes on day 1 for the 2 days left
+      const lateStake = Math.floor((STAKE_AMOUNT * 2) / 3);
+
+      const tooLate = Keypair.generate();
+      await airdrop(tooLate.publicKey);
+      const tooLateTokenAccount = await setupTokenAccount(tooLate, STAKE_AMOUNT);
+
+      const { users } = await runSettledChallenge(
+        {
+          challengeId: "rewards-late-join-001",
+          totalDays: 3,
+          overrides: {
+            lateJoinDays: 2, // joins allowed during days 0 and 1
+          },
+          joinDays: [0, 1],
+          onDay: async (day, run) => {
+            const late = run.users[1];
+            if (day === 1) {
+              const lateBal = await getAccount(provider.connection, late.tokenAccount);
+              expect(Number(lateBal.amount)).to.equal(STAKE_AMOUNT - lateStake);
+
+              const lateParticipant = await (program.account as any).participant.fetch(
+                late.participant
+              );
+              expect(lateParticipant.joinedDay).to.equal(1);
+              expect(lateParticipant.stakeDeposited.toNumber()).to.equal(lateStake);
+
+              try {
+                await run.recordProof(late.participant, 0);
+                expect.fail("Should have thrown error");
+              } catch (err: any) {
+                expect(err.toString()).to.include("DayOutOfRange");
+              }
+            } else if (day === 2) {
+              // Past the join window
+              try {
+                await run.join({
+                  user: tooLate,
+                  participant: getParticipantPDA(run.challengePDA, tooLate.publicKey)[0],
+                  tokenAccount: tooLateTokenAccount,
+                });
+                expect.fail("Should have thrown error");
+              } catch (err: any) {
+                expect(err.toString()).to.include("ChallengeStarted");
+              }
+            }
+          },
+        },
+        [[0, 1, 2], [1, 2]]
+      );
+      const [early, late] = users;
+
+      // 2 of 2 days meets the 80% threshold over the late joiner's own window
+      const settledLate = await (program.account as any).participant.fetch(late.participant);
+      expect(settledLate.isWinner).to.be.true;
+      expect(settledLate.payoutAmount.toNumber()).to.equal(lateStake);
+
+      const settledEarly = await (program.account as any).participant.fetch(early.participant);
+      expect(settledEarly.isWinner).to.be.true;
+      expect(settledEarly.payoutAmount.toNumber()).to.equal(STAKE_AMOUNT);
+    });
+
+    it("should weight a late joiner's bonus by their prorated stake", async () => {
+      // The late joiner stakes on day 1 for the 2 days left
+      const { challengePDA, escrowVault, users, claimPayout } = await runSettledChallenge(
+        {
+          challengeId: "rewards-late-join-002",
+          totalDays: 3,
+          overrides: { lateJoinDays: 2 },
+          joinDays: [0, 1, 0],
+        },
+        [[0, 1, 2], [1, 2], []]
+      );
+      const [early, late] = users;
+
+      // The loser's stake is shared by stake: S * S / (S + 2S/3) for the on-time
+      // winner, the rest for the late joiner
+      const lateStake = Math.floor((STAKE_AMOUNT * 2) / 3);
+      const earlyBonus = Math.floor(
+        (STAKE_AMOUNT * STAKE_AMOUNT) / (STAKE_AMOUNT + lateStake)
+      );
+      const lateBonus = STAKE_AMOUNT - earlyBonus;
+
+      const settledEarly = await (program.account as any).participant.fetch(early.participant);
+      expect(settledEarly.payoutAmount.toNumber()).to.equal(STAKE_AMOUNT + earlyBonus);
+
+      const settledLate = await (program.account as any).participant.fetch(late.participant);
+      expect(settledLate.payoutAmount.toNumber()).to.equal(lateStake + lateBonus);
+      expect(lateBonus).to.be.lessThan(earlyBonus);
+
+      await claimPayout(early);
+      await claimPayout(late);
+
+      const challenge = await (program.account as any).challengeEscrow.fetch(challengePDA);
+      expect(challenge.bonusClaimed.toNumber()).to.equal(challenge.bonusPool.toNumber());
+
+      const vaultAccount = await getAccount(provider.connection, escrowVault);
+      expect(Number(vaultAccount.amount)).to.equal(0);
+    });
+
+    it("should let the treasury sweep payouts unclaimed after the deadline", async () => {
+      const challengeId = "rewards-sweep-001";
+      const [challengePDA] = getChallengePDA(challengeId, factoryPDA);
+      const escrowVault = await getEscrowVault(challengePDA);
+
+      const winner = Keypair.generate();
+      await airdrop(winner.publicKey);
+      const winnerTokenAccount = await setupTokenAccount(winner, STAKE_AMOUNT);
+
+      // The challenge keeps the claim window it was created with
+      await applyFactoryUpdate(null, null, null, 2);
+      await program.methods
+        .createChallenge(
+          challengeId,
+          new BN(STAKE_AMOUNT),
+          1,
+          new BN(getFutureTimestamp(2)),
+          challengeParams()
+        )
+        .accountsPartial({
+          creator: creator.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+          tokenMint: usdcMint,
+          escrowVault: escrowVault,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        })
+        .signers([creator])
+        .rpc();
+      await applyFactoryUpdate(null, null, null, 90 * 24 * 60 * 60);
+
+      const [winnerPDA] = getParticipantPDA(challengePDA, winner.publicKey);
+
+      await program.methods
+        .joinChallenge(challengeId)
+        .accountsPartial({
+          user: winner.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+          participant: winnerPDA,
+          userTokenAccount: winnerTokenAccount,
+          escrowVault: escrowVault,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([winner])
+        .rpc();
+
+      await sleep(2500);
+
+      await program.methods
+        .recordProof(challengeId, 0, proofHash("proof-0"))
+        .accountsPartial({
+          oracle: oracle.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+          participant: winnerPDA,
+          proofRecord: getProofRecordPDA(winnerPDA, 0)[0],
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([oracle])
+        .rpc();
+
+      await sleep((DAY_LENGTH_SECONDS + 2) * 1000);
+
+      await program.methods
+        .settleChallenge(challengeId)
+        .accountsPartial({
+          oracle: oracle.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+        })
+        .signers([oracle])
+        .rpc();
+
+      await program.methods
+        .settleParticipant(challengeId)
+        .accountsPartial({
+          oracle: oracle.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+          participant: winnerPDA,
+        })
+        .signers([oracle])
+        .rpc();
+
+      await program.methods
+        .finalizeSettlement(challengeId)
+        .accountsPartial({
+          oracle: oracle.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+        })
+        .signers([oracle])
+        .rpc();
+
+      await assignPayouts(challengeId, challengePDA, [winnerPDA]);
+
+      const treasuryTokenAccount = await anchor.utils.token.associatedAddress({
+        mint: usdcMint,
+        owner: treasury.publicKey,
+      });
+      const sweep = () =>
+        program.methods
+          .sweepUnclaimed(challengeId)
+          .accountsPartial({
+            treasury: treasury.publicKey,
+            factory: factoryPDA,
+            challenge: challengePDA,
+            treasuryTokenAccount: treasuryTokenAccount,
+            escrowVault: escrowVault,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .remainingAccounts([{ pubkey: winnerPDA, isSigner: false, isWritable: true }])
+          .signers([treasury])
+          .rpc();
+
+      try {
+        await sweep();
+        expect.fail("Should have thrown error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("ClaimDeadlineNotReached");
+      }
+
+      await sleep(3000);
+
+      const treasuryBalBefore = await getAccount(provider.connection, treasuryTokenAccount);
+      await sweep();
+      const treasuryBalAfter = await getAccount(provider.connection, treasuryTokenAccount);
+      expect(Number(treasuryBalAfter.amount) - Number(treasuryBalBefore.amount)).to.equal(
+        STAKE_AMOUNT
+      );
+
+      const participant = await (program.account as any).participant.fetch(winnerPDA);
+      expect(participant.swept).to.be.true;
+      expect(participant.payoutClaimed).to.be.true;
+
+      const vaultAccount = await getAccount(provider.connection, escrowVault);
+      expect(Number(vaultAccount.amount)).to.equal(0);
+    });
+
+    it("should release stakes day by day with missed-pool bonus (daily mode)", async () => {
+      const challengeId = "rewards-daily-001";
+      const [challengePDA] = getChallengePDA(challengeId, factoryPDA);
+      const escrowVault = await getEscrowVault(challengePDA);
+
+      const steady = Keypair.generate();
+      const flaky = Keypair.generate();
+      await Promise.all([airdrop(steady.publicKey), airdrop(flaky.publicKey)]);
+
+      const steadyTokenAccount = await setupTokenAccount(steady, STAKE_AMOUNT * 3);
+      const flakyTokenAccount = await setupTokenAccount(flaky, STAKE_AMOUNT * 3);
+
+      const startTs = getFutureTimestamp(2);
+
+      await program.methods
+        .createChallenge(
+          challengeId,
+          new BN(STAKE_AMOUNT),
+          2,
+          new BN(startTs),
+          challengeParams({ payoutMode: { daily: {} } })
+        )
+        .accountsPartial({
+          creator: creator.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+          tokenMint: usdcMint,
+          escrowVault: escrowVault,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        })
+        .signers([creator])
+        .rpc();
+
+      const [steadyPDA] = getParticipantPDA(challengePDA, steady.publicKey);
+      const [flakyPDA] = getParticipantPDA(challengePDA, flaky.publicKey);
+
+      for (const [user, participant, tokenAccount] of [
+        [steady, steadyPDA, steadyTokenAccount],
+        [flaky, flakyPDA, flakyTokenAccount],
+      ] as [Keypair, PublicKey, PublicKey][]) {
+        await program.methods
+          .joinChallenge(challengeId)
+          .accountsPartial({
+            user: user.publicKey,
+            factory: factoryPDA,
+            challenge: challengePDA,
+            participant,
+            userTokenAccount: tokenAccount,
+            escrowVault: escrowVault,
+            systemProgram: SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([user])
+          .rpc();
+      }
+
+      const recordProof = (participant: PublicKey, day: number) =>
+        program.methods
+          .recordProof(challengeId, day, proofHash(`proof-${day}`))
+          .accountsPartial({
+            oracle: oracle.publicKey,
+            factory: factoryPDA,
+            challenge: challengePDA,
+            participant,
+            proofRecord: getProofRecordPDA(participant, day)[0],
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([oracle])
+          .rpc();
+
+      // Day 0: both show up.
+      await sleep(2500);
+      await recordProof(steadyPDA, 0);
+      await recordProof(flakyPDA, 0);
+
+      // Day 1: only the steady participant shows up.
+      await sleep(DAY_LENGTH_SECONDS * 1000);
+      await recordProof(steadyPDA, 1);
+
+      // Wait for day 1 to end, then settle both days in order.
+      await sleep((DAY_LENGTH_SECONDS + 1) * 1000);
+
+      const dayPDAs = [0, 1].map((day) => getDailySettlementPDA(challengePDA, day)[0]);
+      for (const [day, dailySettlement] of dayPDAs.entries()) {
+        await program.methods
+          .settleDay(challengeId, day)
+          .accountsPartial({
+            oracle: oracle.publicKey,
+            factory: factoryPDA,
+            challenge: challengePDA,
+            dailySettlement,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([oracle])
+          .rpc();
+      }
+
+      const day1 = await (program.account as any).dailySettlement.fetch(dayPDAs[1]);
+      expect(day1.showedUp).to.equal(1);
+      expect(day1.missed).to.equal(1);
+      expect(day1.bonusPerPerson.toNumber()).to.equal(STAKE_AMOUNT / 2);
+
+      const challenge = await (program.account as any).challengeEscrow.fetch(challengePDA);
+      expect(challenge.status.settled).to.not.be.undefined;
+
+      const claimDaily = async (
+        user: Keypair,
+        participant: PublicKey,
+        tokenAccount: PublicKey
+      ): Promise<number> => {
+        const before = await getAccount(provider.connection, tokenAccount);
+        await program.methods
+          .claimDailyPayout(challengeId)
+          .accountsPartial({
+            user: user.publicKey,
+            factory: factoryPDA,
+            challenge: challengePDA,
+            participant,
+            userTokenAccount: tokenAccount,
+            escrowVault: escrowVault,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .remainingAccounts(
+            dayPDAs.map((pubkey) => ({ pubkey, isSigner: false, isWritable: false }))
+          )
+          .signers([user])
+          .rpc();
+        const after = await getAccount(provider.connection, tokenAccount);
+        return Number(after.amount) - Number(before.amount);
+      };
+
+      // Steady: half stake per day + the flaky participant's missed half.
+      expect(await claimDaily(steady, steadyPDA, steadyTokenAccount)).to.equal(
+        STAKE_AMOUNT * 1.5
+      );
+      // Flaky: only day 0's base release.
+      expect(await claimDaily(flaky, flakyPDA, flakyTokenAccount)).to.equal(
+        STAKE_AMOUNT / 2
+      );
+
+      const vaultAccount = await getAccount(provider.connection, escrowVault);
+      expect(Number(vaultAccount.amount)).to.equal(0);
+    });
+
+    it("should sweep unclaimed daily accruals after the deadline (daily mode)", async () => {
+      const challengeId = "rewards-daily-sweep-001";
+      const [challengePDA] = getChallengePDA(challengeId, factoryPDA);
+      const escrowVault = await getEscrowVault(challengePDA);
+
+      const user = Keypair.generate();
+      await airdrop(user.publicKey);
+      const userTokenAccount = await setupTokenAccount(user, STAKE_AMOUNT);
+
+      // Short claim window so the sweep can run in the test
+      await applyFactoryUpdate(null, null, null, 2);
+      await program.methods
+        .createChallenge(
+          challengeId,
+          new BN(STAKE_AMOUNT),
+          1,
+          new BN(getFutureTimestamp(2)),
+          challengeParams({ payoutMode: { daily: {} } })
+        )
+        .accountsPartial({
+          creator: creator.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+          tokenMint: usdcMint,
+          escrowVault: escrowVault,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        })
+        .signers([creator])
+        .rpc();
+      await applyFactoryUpdate(null, null, null, 90 * 24 * 60 * 60);
+
+      const [participantPDA] = getParticipantPDA(challengePDA, user.publicKey);
+      await program.methods
+        .joinChallenge(challengeId)
+        .accountsPartial({
+          user: user.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+          participant: participantPDA,
+          userTokenAccount,
+          escrowVault: escrowVault,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user])
+        .rpc();
+
+      await sleep(2500);
+      await program.methods
+        .recordProof(challengeId, 0, proofHash("proof-0"))
+        .accountsPartial({
+          oracle: oracle.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+          participant: participantPDA,
+          proofRecord: getProofRecordPDA(participantPDA, 0)[0],
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([oracle])
+        .rpc();
+
+      await sleep((DAY_LENGTH_SECONDS + 1) * 1000);
+
+      const [dailySettlement] = getDailySettlementPDA(challengePDA, 0);
+      await program.methods
+        .settleDay(challengeId, 0)
+        .accountsPartial({
+          oracle: oracle.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+          dailySettlement,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([oracle])
+        .rpc();
+
+      const closeDailySettlement = () =>
+        program.methods
+          .closeDailySettlement(challengeId, 0)
+          .accountsPartial({
+            payer: oracle.publicKey,
+            factory: factoryPDA,
+            challenge: challengePDA,
+            dailySettlement,
+          })
+          .signers([oracle])
+          .rpc();
+
+      // The day's accrual is still claimable, so its settlement must stay open
+      try {
+        await closeDailySettlement();
+        expect.fail("Should have thrown error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("PendingWinnerPayouts");
+      }
+
+      // The participant never claims; the accrual is swept without participant accounts
+      await sleep(3000);
+
+      const treasuryTokenAccount = await anchor.utils.token.associatedAddress({
+        mint: usdcMint,
+        owner: treasury.publicKey,
+      });
+      const treasuryBalBefore = await getAccount(provider.connection, treasuryTokenAccount);
+      await program.methods
+        .sweepUnclaimed(challengeId)
+        .accountsPartial({
+          treasury: treasury.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+          treasuryTokenAccount: treasuryTokenAccount,
+          escrowVault: escrowVault,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([treasury])
+        .rpc();
+      const treasuryBalAfter = await getAccount(provider.connection, treasuryTokenAccount);
+      expect(Number(treasuryBalAfter.amount) - Number(treasuryBalBefore.amount)).to.equal(
+        STAKE_AMOUNT
+      );
+
+      const challenge = await (program.account as any).challengeEscrow.fetch(challengePDA);
+      expect(challenge.dailyClaimed.toNumber()).to.equal(challenge.dailyReleased.toNumber());
+
+      // Swept accruals can no longer be claimed
+      try {
+        await program.methods
+          .claimDailyPayout(challengeId)
+          .accountsPartial({
+            user: user.publicKey,
+            factory: factoryPDA,
+            challenge: challengePDA,
+            participant: participantPDA,
+            userTokenAccount,
+            escrowVault: escrowVault,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .remainingAccounts([{ pubkey: dailySettlement, isSigner: false, isWritable: false }])
+          .signers([user])
+          .rpc();
+        expect.fail("Should have thrown error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("PayoutsSwept");
+      }
+
+      // With every accrual swept, the oracle reclaims the settlement rent
+      await closeDailySettlement();
+      expect(await provider.connection.getAccountInfo(dailySettlement)).to.be.null;
+
+      const vaultAccount = await getAccount(provider.connection, escrowVault);
+      expect(Number(vaultAccount.amount)).to.equal(0);
+
+      // Nothing is owed any more, so the participant can close
+      await program.methods
+        .closeParticipant(challengeId)
+        .accountsPartial({
+          authority: user.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+          participant: participantPDA,
+          destination: user.publicKey,
+        })
+        .signers([user])
+        .rpc();
+      expect(await provider.connection.getAccountInfo(participantPDA)).to.be.null;
+    });
+  });
+
+  // ============================================================
+  // PROOF REVOCATION TESTS
+  // ============================================================
+
+  describe("Proof Revocation", () => {
+    const DAY_LENGTH_SECONDS = 3;
+    const challengeId = "revoke-test-001";
+    let challengePDA: PublicKey;
+    let escrowVault: PublicKey;
+    let keeper: Keypair;
+    let disputed: Keypair;
+    let keeperPDA: PublicKey;
+    let disputedPDA: PublicKey;
+    let keeperTokenAccount: PublicKey;
+    let disputedTokenAccount: PublicKey;
+
+    const recordProof = (participant: PublicKey, label: string) =>
+      program.methods
+        .recordProof(challengeId, 0, proofHash(label))
+        .accountsPartial({
+          oracle: oracle.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+          participant,
+          proofRecord: getProofRecordPDA(participant, 0)[0],
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([oracle])
+        .rpc();
+
+    const revokeProof = (
+      id: string,
+      challenge: PublicKey,
+      participant: PublicKey,
+      reasonCode: number
+    ) =>
+      program.methods
+        .revokeProof(id, 0, reasonCode)
+        .accountsPartial({
+          oracle: oracle.publicKey,
+          factory: factoryPDA,
+          challenge,
+          participant,
+          proofRecord: getProofRecordPDA(participant, 0)[0],
+          payer: oracle.publicKey,
+        })
+        .signers([oracle])
+        .rpc();
+
+    const settleParticipant = (participant: PublicKey) =>
+      program.methods
+        .settleParticipant(challengeId)
+        .accountsPartial({
+          oracle: oracle.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+          participant,
+        })
+        .signers([oracle])
+        .rpc();
+
+    const closeProofRecord = (participant: PublicKey, payer: Keypair) =>
+      program.methods
+        .closeProofRecord(challengeId, 0)
+        .accountsPartial({
+          payer: payer.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+          participant,
+          proofRecord: getProofRecordPDA(participant, 0)[0],
+        })
+        .signers([payer])
+        .rpc();
+
+    async function expectRevokeToFail(
+      id: string,
+      challenge: PublicKey,
+      participant: PublicKey,
+      expected: string
+    ) {
+      try {
+        await revokeProof(id, challenge, participant, 1);
+        expect.fail("Should have thrown error");
+      } catch (err: any) {
+        expect(err.toString()).to.include(expected);
+      }
+    }
+
+    before(async () => {
+      await applyFactoryUpdate(DAY_LENGTH_SECONDS, 0);
+
+      keeper = Keypair.generate();
+      disputed = Keypair.generate();
+      await Promise.all([airdrop(keeper.publicKey), airdrop(disputed.publicKey)]);
+      keeperTokenAccount = await setupTokenAccount(keeper, STAKE_AMOUNT * 2);
+      disputedTokenAccount = await setupTokenAccount(disputed, STAKE_AMOUNT);
+
+      [challengePDA] = getChallengePDA(challengeId, factoryPDA);
+      escrowVault = await getEscrowVault(challengePDA);
+
+      await program.methods
+        .createChallenge(
+          challengeId,
+          new BN(STAKE_AMOUNT),
+          1,
+          new BN(getFutureTimestamp(3)),
+          challengeParams()
+        )
+        .accountsPartial({
+          creator: creator.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+          tokenMint: usdcMint,
+          escrowVault: escrowVault,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        })
+        .signers([creator])
+        .rpc();
+
+      [keeperPDA] = getParticipantPDA(challengePDA, keeper.publicKey);
+      [disputedPDA] = getParticipantPDA(challengePDA, disputed.publicKey);
+
+      for (const [user, participant, tokenAccount] of [
+        [keeper, keeperPDA, keeperTokenAccount],
+        [disputed, disputedPDA, disputedTokenAccount],
+      ] as [Keypair, PublicKey, PublicKey][]) {
+        await program.methods
+          .joinChallenge(challengeId)
+          .accountsPartial({
+            user: user.publicKey,
+            factory: factoryPDA,
+            challenge: challengePDA,
+            participant,
+            userTokenAccount: tokenAccount,
+            escrowVault: escrowVault,
+            systemProgram: SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([user])
+          .rpc();
+      }
+    });
+
+    after(async () => {
+      await applyFactoryUpdate(24 * 60 * 60, 24 * 60 * 60);
+    });
+
+    it("should reject revocation while Created (no proofs yet)", async () => {
+      await expectRevokeToFail(
+        challengeId,
+        challengePDA,
+        disputedPDA,
+        "AccountNotInitialized"
+      );
+    });
+
+    it("should revoke and re-record a proof while Started", async () => {
+      await sleep(3500);
+      await recordProof(keeperPDA, "keeper-0");
+      await recordProof(disputedPDA, "disputed-0");
+
+      const challenge = await (program.account as any).challengeEscrow.fetch(
+        challengePDA
+      );
+      expect(challenge.status.started).to.not.be.undefined;
+
+      await revokeProof(challengeId, challengePDA, disputedPDA, 1);
+
+      let participant = await (program.account as any).participant.fetch(
+        disputedPDA
+      );
+      expect(participant.proofDays).to.equal(0);
+      expect(participant.proofBitmap[0] & 1).to.equal(0);
+      const closed = await (program.account as any).proofRecord.fetchNullable(
+        getProofRecordPDA(disputedPDA, 0)[0]
+      );
+      expect(closed).to.be.null;
+
+      // Correction: the oracle can record a replacement proof for the same day
+      await recordProof(disputedPDA, "disputed-0-corrected");
+      participant = await (program.account as any).participant.fetch(disputedPDA);
+      expect(participant.proofDays).to.equal(1);
+    });
+
+    it("should keep proof records open while the proof can still change", async () => {
+      try {
+        await closeProofRecord(keeperPDA, oracle);
+        expect.fail("Should have thrown error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("ChallengeStillActive");
+      }
+    });
+
+    it("should revoke a proof while Ended, before the participant is settled", async () => {
+      await sleep((DAY_LENGTH_SECONDS + 1) * 1000);
+
+      await program.methods
+        .settleChallenge(challengeId)
+        .accountsPartial({
+          oracle: oracle.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+        })
+        .signers([oracle])
+        .rpc();
+
+      await revokeProof(challengeId, challengePDA, disputedPDA, 2);
+
+      await settleParticipant(disputedPDA);
+      const participant = await (program.account as any).participant.fetch(
+        disputedPDA
+      );
+      expect(participant.isWinner).to.be.false;
+    });
+
+    it("should reject revocation once the participant is settled", async () => {
+      await settleParticipant(keeperPDA);
+      await expectRevokeToFail(
+        challengeId,
+        challengePDA,
+        keeperPDA,
+        "AlreadySettled"
+      );
+    });
+
+    it("should reject revocation while Settled", async () => {
+      await program.methods
+        .finalizeSettlement(challengeId)
+        .accountsPartial({
+          oracle: oracle.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+        })
+        .signers([oracle])
+        .rpc();
+
+      await expectRevokeToFail(
+        challengeId,
+        challengePDA,
+        keeperPDA,
+        "InvalidChallengeStatus"
+      );
+    });
+
+    it("should refund proof record rent to the payer once final", async () => {
+      const [proofRecordPDA] = getProofRecordPDA(keeperPDA, 0);
+
+      // Only the account that paid the rent can reclaim it
+      try {
+        await closeProofRecord(keeperPDA, keeper);
+        expect.fail("Should have thrown error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("ConstraintAddress");
+      }
+
+      const rent = await provider.connection.getBalance(proofRecordPDA);
+      const oracleBefore = await provider.connection.getBalance(oracle.publicKey);
+      await closeProofRecord(keeperPDA, oracle);
+      const oracleAfter = await provider.connection.getBalance(oracle.publicKey);
+
+      const closed = await (program.account as any).proofRecord.fetchNullable(
+        proofRecordPDA
+      );
+      expect(closed).to.be.null;
+      expect(oracleAfter - oracleBefore).to.equal(rent);
+    });
+
+    it("should reject revocation while Cancelled", async () => {
+      const cancelledId = "revoke-cancel-001";
+      const [cancelledPDA] = getChallengePDA(cancelledId, factoryPDA);
+      const cancelledVault = await getEscrowVault(cancelledPDA);
+
+      await program.methods
+        .createChallenge(
+          cancelledId,
+          new BN(STAKE_AMOUNT),
+          1,
+          new BN(getFutureTimestamp(300)),
+          challengeParams()
+        )
+        .accountsPartial({
+          creator: creator.publicKey,
+          factory: factoryPDA,
+          challenge: cancelledPDA,
+          tokenMint: usdcMint,
+          escrowVault: cancelledVault,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        })
+        .signers([creator])
+        .rpc();
+
+      const [participantPDA] = getParticipantPDA(cancelledPDA, keeper.publicKey);
+      await program.methods
+        .joinChallenge(cancelledId)
+        .accountsPartial({
+          user: keeper.publicKey,
+          factory: factoryPDA,
+          challenge: cancelledPDA,
+          participant: participantPDA,
+          userTokenAccount: keeperTokenAccount,
+          escrowVault: cancelledVault,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([keeper])
+        .rpc();
+
+      await program.methods
+        .cancelChallenge(cancelledId)
+        .accountsPartial({
+          creator: creator.publicKey,
+          factory: factoryPDA,
+          challenge: cancelledPDA,
+        })
+        .signers([creator])
+        .rpc();
+
+      // Cancellation is only possible before start, so no proof can exist
+      await expectRevokeToFail(
+        cancelledId,
+        cancelledPDA,
+        participantPDA,
+        "AccountNotInitialized"
+      );
+    });
+
+    it("should reject revocation while Paused", async () => {
+      const pausedId = "revoke-pause-001";
+      const [pausedPDA] = getChallengePDA(pausedId, factoryPDA);
+      const pausedVault = await getEscrowVault(pausedPDA);
+
+      await program.methods
+        .createChallenge(
+          pausedId,
+          new BN(STAKE_AMOUNT),
+          2,
+          new BN(getFutureTimestamp(3)),
+          challengeParams()
+        )
+        .accountsPartial({
+          creator: creator.publicKey,
+          factory: factoryPDA,
+          challenge: pausedPDA,
+          tokenMint: usdcMint,
+          escrowVault: pausedVault,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        })
+        .signers([creator])
+        .rpc();
+
+      const pausedUser = Keypair.generate();
+      await airdrop(pausedUser.publicKey);
+      const [participantPDA] = getParticipantPDA(pausedPDA, pausedUser.publicKey);
+      await program.methods
+        .joinChallenge(pausedId)
+        .accountsPartial({
+          user: pausedUser.publicKey,
+          factory: factoryPDA,
+          challenge: pausedPDA,
+          participant: participantPDA,
+          userTokenAccount: await setupTokenAccount(pausedUser, STAKE_AMOUNT),
+          escrowVault: pausedVault,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([pausedUser])
+        .rpc();
+
+      await sleep(3500);
+      await program.methods
+        .recordProof(pausedId, 0, proofHash("paused-0"))
+        .accountsPartial({
+          oracle: oracle.publicKey,
+          factory: factoryPDA,
+          challenge: pausedPDA,
+          participant: participantPDA,
+          proofRecord: getProofRecordPDA(participantPDA, 0)[0],
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([oracle])
+        .rpc();
+
+      await program.methods
+        .pauseChallenge(pausedId)
+        .accountsPartial({
+          signer: oracle.publicKey,
+          factory: factoryPDA,
+          challenge: pausedPDA,
+        })
+        .signers([oracle])
+        .rpc();
+
+      await expectRevokeToFail(
+        pausedId,
+        pausedPDA,
+        participantPDA,
+        "InvalidChallengeStatus"
+      );
+    });
+  });
+
+  // ============================================================
+  // DISPUTE WINDOW TESTS
+  // ============================================================
+
+  describe("Dispute Window", () => {
+    const DAY_LENGTH_SECONDS = 3;
+    const DISPUTE_WINDOW_SECONDS = 6;
+    const challengeId = "dispute-test-001";
+    let challengePDA: PublicKey;
+    let escrowVault: PublicKey;
+    let upheldUser: Keypair;
+    let rejectedUser: Keypair;
+    let lateUser: Keypair;
+    let upheldPDA: PublicKey;
+    let rejectedPDA: PublicKey;
+    let latePDA: PublicKey;
+
+    const getDisputePDA = (participant: PublicKey, dayIndex: number) => {
+      const dayBuf = Buffer.alloc(4);
+      dayBuf.writeUInt32LE(dayIndex);
+      return PublicKey.findProgramAddressSync(
+        [Buffer.from("dispute"), participant.toBuffer(), dayBuf],
+        PROGRAM_ID
+      )[0];
+    };
+
+    const openDispute = (user: Keypair, participant: PublicKey) =>
+      program.methods
+        .openDispute(challengeId, 0)
+        .accountsPartial({
+          user: user.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+          participant,
+          dispute: getDisputePDA(participant, 0),
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user])
+        .rpc();
+
+    const resolveDispute = (user: Keypair, participant: PublicKey, upheld: boolean) =>
+      program.methods
+        .resolveDispute(challengeId, 0, upheld)
+        .accountsPartial({
+          oracle: oracle.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+          participant,
+          dispute: getDisputePDA(participant, 0),
+          user: user.publicKey,
+        })
+        .signers([oracle])
+        .rpc();
+
+    const recordProof = (participant: PublicKey, label: string, disputes: PublicKey[] = []) =>
+      program.methods
+        .recordProof(challengeId, 0, proofHash(label))
+        .accountsPartial({
+          oracle: oracle.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+          participant,
+          proofRecord: getProofRecordPDA(participant, 0)[0],
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(
+          disputes.map((pubkey) => ({ pubkey, isSigner: false, isWritable: false }))
+        )
+        .signers([oracle])
+        .rpc();
+
+    const settleParticipant = (participant: PublicKey) =>
+      program.methods
+        .settleParticipant(challengeId)
+        .accountsPartial({
+          oracle: oracle.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+          participant,
+        })
+        .signers([oracle])
+        .rpc();
+
+    before(async () => {
+      await applyFactoryUpdate(DAY_LENGTH_SECONDS, DISPUTE_WINDOW_SECONDS);
+
+      upheldUser = Keypair.generate();
+      rejectedUser = Keypair.generate();
+      lateUser = Keypair.generate();
+      await Promise.all([
+        airdrop(upheldUser.publicKey),
+        airdrop(rejectedUser.publicKey),
+        airdrop(lateUser.publicKey),
+      ]);
+      const upheldTokenAccount = await setupTokenAccount(upheldUser, STAKE_AMOUNT);
+      const rejectedTokenAccount = await setupTokenAccount(rejectedUser, STAKE_AMOUNT);
+      const lateTokenAccount = await setupTokenAccount(lateUser, STAKE_AMOUNT);
+
+      [challengePDA] = getChallengePDA(challengeId, factoryPDA);
+      escrowVault = await getEscrowVault(challengePDA);
+
+      await program.methods
+        .createChallenge(
+          challengeId,
+          new BN(STAKE_AMOUNT),
+          1,
+          new BN(getFutureTimestamp(3)),
+          challengeParams()
+        )
+        .accountsPartial({
+          creator: creator.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+          tokenMint: usdcMint,
+          escrowVault: escrowVault,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        })
+        .signers([creator])
+        .rpc();
+
+      [upheldPDA] = getParticipantPDA(challengePDA, upheldUser.publicKey);
+      [rejectedPDA] = getParticipantPDA(challengePDA, rejectedUser.publicKey);
+      [latePDA] = getParticipantPDA(challengePDA, lateUser.publicKey);
+
+      for (const [user, participant, tokenAccount] of [
+        [upheldUser, upheldPDA, upheldTokenAccount],
+        [rejectedUser, rejectedPDA, rejectedTokenAccount],
+        [lateUser, latePDA, lateTokenAccount],
+      ] as [Keypair, PublicKey, PublicKey][]) {
+        await program.methods
+          .joinChallenge(challengeId)
+          .accountsPartial({
+            user: user.publicKey,
+            factory: factoryPDA,
+            challenge: challengePDA,
+            participant,
+            userTokenAccount: tokenAccount,
+            escrowVault: escrowVault,
+            systemProgram: SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([user])
+          .rpc();
+      }
+    });
+
+    after(async () => {
+      await applyFactoryUpdate(24 * 60 * 60, 24 * 60 * 60);
+    });
+
+    it("should let participants dispute a day without a proof", async () => {
+      await sleep(3500);
+      await openDispute(upheldUser, upheldPDA);
+      await openDispute(rejectedUser, rejectedPDA);
+      await openDispute(lateUser, latePDA);
+
+      const participant = await (program.account as any).participant.fetch(
+        upheldPDA
+      );
+      expect(participant.openDisputes).to.equal(1);
+      const challenge = await (program.account as any).challengeEscrow.fetch(
+        challengePDA
+      );
+      expect(challenge.openDisputes).to.equal(3);
+    });
+
+    it("should block settle_participant during the dispute window", async () => {
+      await sleep((DAY_LENGTH_SECONDS + 1) * 1000);
+
+      await program.methods
+        .settleChallenge(challengeId)
+        .accountsPartial({
+          oracle: oracle.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+        })
+        .signers([oracle])
+        .rpc();
+
+      try {
+        await settleParticipant(upheldPDA);
+        expect.fail("Should have thrown error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("DisputeWindowOpen");
+      }
+    });
+
+    it("should accept a late proof for an upheld dispute", async () => {
+      await recordProof(upheldPDA, "upheld-0");
+
+      // The day is now proven, so the dispute can't be rejected
+      try {
+        await resolveDispute(upheldUser, upheldPDA, false);
+        expect.fail("Should have thrown error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("DisputeOutcomeMismatch");
+      }
+
+      await resolveDispute(upheldUser, upheldPDA, true);
+
+      const participant = await (program.account as any).participant.fetch(
+        upheldPDA
+      );
+      expect(participant.proofDays).to.equal(1);
+      expect(participant.openDisputes).to.equal(0);
+    });
+
+    it("should only accept a proof after the window for a disputed day", async () => {
+      await sleep((DISPUTE_WINDOW_SECONDS + 1) * 1000);
+
+      // Without a dispute, or with another participant's, the day stays closed
+      for (const disputes of [[], [getDisputePDA(rejectedPDA, 0)]]) {
+        try {
+          await recordProof(latePDA, "late-0", disputes);
+          expect.fail("Should have thrown error");
+        } catch (err: any) {
+          expect(err.toString()).to.include("ChallengeEnded");
+        }
+      }
+
+      await recordProof(latePDA, "late-0", [getDisputePDA(latePDA, 0)]);
+      await resolveDispute(lateUser, latePDA, true);
+
+      const participant = await (program.account as any).participant.fetch(latePDA);
+      expect(participant.proofDays).to.equal(1);
+    });
+
+    it("should block settlement until remaining disputes are resolved", async () => {
+      try {
+        await settleParticipant(rejectedPDA);
+        expect.fail("Should have thrown error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("DisputesPending");
+      }
+
+      // Upholding requires the day's proof to be recorded first
+      try {
+        await resolveDispute(rejectedUser, rejectedPDA, true);
+        expect.fail("Should have thrown error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("DisputeOutcomeMismatch");
+      }
+
+      await resolveDispute(rejectedUser, rejectedPDA, false);
+      await settleParticipant(rejectedPDA);
+      await settleParticipant(upheldPDA);
+
+      const upheld = await (program.account as any).participant.fetch(upheldPDA);
+      const rejected = await (program.account as any).participant.fetch(
+        rejectedPDA
+      );
+      expect(upheld.isWinner).to.be.true;
+      expect(rejected.isWinner).to.be.false;
+    });
+  });
+
+  // ============================================================
+  // ORACLE COMMITTEE TESTS
+  // ============================================================
+
+  describe("Oracle Committee", () => {
+    const challengeId = "committee-test-001";
+    let challengePDA: PublicKey;
+    let participantPDA: PublicKey;
+    let cosigner: Keypair;
+    let outsider: Keypair;
+
+    const setCommittee = async (members: PublicKey[], threshold: number) => {
+      await program.methods
+        .setOracleCommittee(members, threshold)
+        .accountsPartial({
+          authority: authority.publicKey,
+          factory: factoryPDA,
+        })
+        .signers([authority])
+        .rpc();
+      await program.methods
+        .executePendingUpdate()
+        .accountsPartial({
+          authority: authority.publicKey,
+          factory: factoryPDA,
+        })
+        .signers([authority])
+        .rpc();
+    };
+
+    const recordProof = (extraSigners: Keypair[]) =>
+      program.methods
+        .recordProof(challengeId, 0, proofHash("committee-proof-0"))
+        .accountsPartial({
+          oracle: oracle.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+          participant: participantPDA,
+          proofRecord: getProofRecordPDA(participantPDA, 0)[0],
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(
+          extraSigners.map((signer) => ({
+            pubkey: signer.publicKey,
+            isSigner: true,
+            isWritable: false,
+          }))
+        )
+        .signers([oracle, ...extraSigners])
+        .rpc();
+
+    before(async () => {
+      cosigner = Keypair.generate();
+      outsider = Keypair.generate();
+      const user = Keypair.generate();
+      await airdrop(user.publicKey);
+      const userTokenAccount = await setupTokenAccount(user, STAKE_AMOUNT);
+
+      [challengePDA] = getChallengePDA(challengeId, factoryPDA);
+      const escrowVault = await getEscrowVault(challengePDA);
+
+      await program.methods
+        .createChallenge(
+          challengeId,
+          new BN(STAKE_AMOUNT),
+          TOTAL_DAYS,
+          new BN(getFutureTimestamp(2)),
+          challengeParams()
+        )
+        .accountsPartial({
+          creator: creator.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+          tokenMint: usdcMint,
+          escrowVault: escrowVault,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        })
+        .signers([creator])
+        .rpc();
+
+      [participantPDA] = getParticipantPDA(challengePDA, user.publicKey);
+      await program.methods
+        .joinChallenge(challengeId)
+        .accountsPartial({
+          user: user.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+          participant: participantPDA,
+          userTokenAccount,
+          escrowVault: escrowVault,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user])
+        .rpc();
+
+      await sleep(3000);
+    });
+
+    after(async () => {
+      await setCommittee([], 1);
+    });
+
+    it("should reject an invalid committee", async () => {
+      try {
+        await setCommittee([cosigner.publicKey, cosigner.publicKey], 2);
+        expect.fail("Should have thrown error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("InvalidOracleCommittee");
+      }
+
+      try {
+        await setCommittee([cosigner.publicKey], 3);
+        expect.fail("Should have thrown error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("InvalidOracleThreshold");
+      }
+    });
+
+    it("should set a 2-of-2 committee", async () => {
+      await setCommittee([cosigner.publicKey], 2);
+
+      const factory = await (program.account as any).escrowFactory.fetch(
+        factoryPDA
+      );
+      expect(factory.oracleCommittee.map((k: PublicKey) => k.toBase58())).to.deep.equal([
+        cosigner.publicKey.toBase58(),
+      ]);
+      expect(factory.oracleThreshold).to.equal(2);
+
+      // Existing challenges keep the oracle set they were created with
+      const challenge = await (program.account as any).challengeEscrow.fetch(
+        challengePDA
+      );
+      expect(challenge.oracle.toBase58()).to.equal(oracle.publicKey.toBase58());
+      expect(challenge.oracleCommittee).to.have.length(0);
+      expect(challenge.oracleThreshold).to.equal(1);
+    });
+
+    it("should only let the creator migrate a challenge to the new committee", async () => {
+      try {
+        await program.methods
+          .migrateChallengeOracles(challengeId)
+          .accountsPartial({
+            creator: outsider.publicKey,
+            factory: factoryPDA,
+            challenge: challengePDA,
+          })
+          .signers([outsider])
+          .rpc();
+        expect.fail("Should have thrown error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("Unauthorized");
+      }
+
+      await program.methods
+        .migrateChallengeOracles(challengeId)
+        .accountsPartial({
+          creator: creator.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+        })
+        .signers([creator])
+        .rpc();
+
+      const challenge = await (program.account as any).challengeEscrow.fetch(
+        challengePDA
+      );
+      expect(challenge.oracleCommittee.map((k: PublicKey) => k.toBase58())).to.deep.equal([
+        cosigner.publicKey.toBase58(),
+      ]);
+      expect(challenge.oracleThreshold).to.equal(2);
+    });
+
+    it("should fail with a single oracle signature", async () => {
+      try {
+        await recordProof([]);
+        expect.fail("Should have thrown error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("OracleQuorumNotMet");
+      }
+    });
+
+    it("should not count signers outside the committee", async () => {
+      try {
+        await recordProof([outsider]);
+        expect.fail("Should have thrown error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("OracleQuorumNotMet");
+      }
+    });
+
+    it("should record a proof co-signed by the committee", async () => {
+      await recordProof([cosigner]);
+
+      const participant = await (program.account as any).participant.fetch(
+        participantPDA
+      );
+      expect(participant.proofDays).to.equal(1);
+    });
+
+    it("should require the committee quorum to end a challenge", async () => {
+      const settleChallenge = (extraSigners: Keypair[]) =>
+        program.methods
+          .settleChallenge(challengeId)
+          .accountsPartial({
+            oracle: oracle.publicKey,
+            factory: factoryPDA,
+            challenge: challengePDA,
+          })
+          .remainingAccounts(
+            extraSigners.map((signer) => ({
+              pubkey: signer.publicKey,
+              isSigner: true,
+              isWritable: false,
+            }))
+          )
+          .signers([oracle, ...extraSigners])
+          .rpc();
+
+      try {
+        await settleChallenge([]);
+        expect.fail("Should have thrown error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("OracleQuorumNotMet");
+      }
+
+      // With the quorum met, only the timing check remains
+      try {
+        await settleChallenge([cosigner]);
+        expect.fail("Should have thrown error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("ChallengeNotEnded");
+      }
+    });
+
+    it("should require the committee quorum to pause and resume", async () => {
+      const pauseAs = (method: "pauseChallenge" | "resumeChallenge", signers: Keypair[]) =>
+        program.methods[method](challengeId)
+          .accountsPartial({
+            signer: signers[0].publicKey,
+            factory: factoryPDA,
+            challenge: challengePDA,
+          })
+          .remainingAccounts(
+            signers.slice(1).map((signer) => ({
+              pubkey: signer.publicKey,
+              isSigner: true,
+              isWritable: false,
+            }))
+          )
+          .signers(signers)
+          .rpc();
+
+      // A single committee member is not enough
+      try {
+        await pauseAs("pauseChallenge", [cosigner]);
+        expect.fail("Should have thrown error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("OracleQuorumNotMet");
+      }
+
+      await pauseAs("pauseChallenge", [cosigner, oracle]);
+      let challenge = await (program.account as any).challengeEscrow.fetch(challengePDA);
+      expect(challenge.status.paused).to.not.be.undefined;
+
+      try {
+        await pauseAs("resumeChallenge", [oracle]);
+        expect.fail("Should have thrown error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("OracleQuorumNotMet");
+      }
+
+      await pauseAs("resumeChallenge", [oracle, cosigner]);
+      challenge = await (program.account as any).challengeEscrow.fetch(challengePDA);
+      expect(challenge.status.paused).to.be.undefined;
+    });
+  });
+
+  // ============================================================
+  // ATTESTED PROOF TESTS
+  // ============================================================
+
+  describe("Attested Proofs", () => {
+    const challengeId = "attested-test-001";
+    let challengePDA: PublicKey;
+    let participantPDA: PublicKey;
+    let user: Keypair;
+
+    const attestationMessage = (
+      dayIndex: number,
+      hash: number[],
+      revocationCount: number,
+      expiresAt: number
+    ) => {
+      const dayBuf = Buffer.alloc(4);
+      dayBuf.writeUInt32LE(dayIndex);
+      const revocationBuf = Buffer.alloc(4);
+      revocationBuf.writeUInt32LE(revocationCount);
+      const expiryBuf = Buffer.alloc(8);
+      expiryBuf.writeBigInt64LE(BigInt(expiresAt));
+      return Buffer.concat([
+        Buffer.from("proven-stake:proof-attestation:v2"),
+        challengePDA.toBuffer(),
+        user.publicKey.toBuffer(),
+        dayBuf,
+        Buffer.from(hash),
+        revocationBuf,
+        expiryBuf,
+      ]);
+    };
+
+    const submitAttestedProof = (
+      signer: Keypair,
+      signedDay: number,
+      expiresAt: number,
+      revocationCount = 0
+    ) => {
+      const hash = proofHash("attested-proof-0");
+      return program.methods
+        .submitAttestedProof(challengeId, 0, hash, new BN(expiresAt))
+        .accountsPartial({
+          user: user.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+          participant: participantPDA,
+          proofRecord: getProofRecordPDA(participantPDA, 0)[0],
+          instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+          systemProgram: SystemProgram.programId,
+        })
+        .preInstructions([
+          Ed25519Program.createInstructionWithPrivateKey({
+            privateKey: signer.secretKey,
+            message: attestationMessage(signedDay, hash, revocationCount, expiresAt),
+          }),
+        ])
+        .signers([user])
+        .rpc();
+    };
+
+    before(async () => {
+      user = Keypair.generate();
+      await airdrop(user.publicKey);
+      const userTokenAccount = await setupTokenAccount(user, STAKE_AMOUNT);
+
+      [challengePDA] = getChallengePDA(challengeId, factoryPDA);
+      const escrowVault = await getEscrowVault(challengePDA);
+
+      await program.methods
+        .createChallenge(
+          challengeId,
+          new BN(STAKE_AMOUNT),
+          TOTAL_DAYS,
+          new BN(getFutureTimestamp(2)),
+          challengeParams()
+        )
+        .accountsPartial({
+          creator: creator.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+          tokenMint: usdcMint,
+          escrowVault: escrowVault,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        })
+        .signers([creator])
+        .rpc();
+
+      [participantPDA] = getParticipantPDA(challengePDA, user.publicKey);
+      await program.methods
+        .joinChallenge(challengeId)
+        .accountsPartial({
+          user: user.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+          participant: participantPDA,
+          userTokenAccount,
+          escrowVault: escrowVault,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user])
+        .rpc();
+
+      await sleep(3000);
+    });
+
+    it("should reject an expired attestation", async () => {
+      try {
+        await submitAttestedProof(oracle, 0, getFutureTimestamp(-60));
+        expect.fail("Should have thrown error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("AttestationExpired");
+      }
+    });
+
+    it("should reject an attestation not signed by the oracle", async () => {
+      try {
+        await submitAttestedProof(Keypair.generate(), 0, getFutureTimestamp(600));
+        expect.fail("Should have thrown error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("InvalidAttestation");
+      }
+    });
+
+    it("should reject an attestation for a different day", async () => {
+      try {
+        await submitAttestedProof(oracle, 1, getFutureTimestamp(600));
+        expect.fail("Should have thrown error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("InvalidAttestation");
+      }
+    });
+
+    let attestationExpiry: number;
+
+    it("should record a user-submitted attested proof", async () => {
+      attestationExpiry = getFutureTimestamp(600);
+      await submitAttestedProof(oracle, 0, attestationExpiry);
+
+      const participant = await (program.account as any).participant.fetch(
+        participantPDA
+      );
+      expect(participant.proofDays).to.equal(1);
+
+      const record = await (program.account as any).proofRecord.fetch(
+        getProofRecordPDA(participantPDA, 0)[0]
+      );
+      expect(record.payer.toBase58()).to.equal(user.publicKey.toBase58());
+      expect(Buffer.from(record.proofHash)).to.deep.equal(
+        Buffer.from(proofHash("attested-proof-0"))
+      );
+    });
+
+    it("should refund the submitting user when the proof is revoked", async () => {
+      const before = await provider.connection.getBalance(user.publicKey);
+
+      await program.methods
+        .revokeProof(challengeId, 0, 1)
+        .accountsPartial({
+          oracle: oracle.publicKey,
+          factory: factoryPDA,
+          challenge: challengePDA,
+          participant: participantPDA,
+          proofRecord: getProofRecordPDA(participantPDA, 0)[0],
+          payer: user.publicKey,
+        })
+        .signers([oracle])
+        .rpc();
+
+      const after = await provider.connection.getBalance(user.publicKey);
+      expect(after).to.be.greaterThan(before);
+
+      const participant = await (program.account as any).participant.fetch(
+        participantPDA
+      );
+      expect(participant.revocationCount).to.equal(1);
+    });
+
+    it("should reject resubmitting a revoked attestation", async () => {
+      try {
+        await submitAttestedProof(oracle, 0, attestationExpiry);
+        expect.fail("Should have thrown error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("InvalidAttestation");
+      }
+
+      // A fresh attestation over the current revocation count is accepted
+      await submitAttestedProof(oracle, 0, attestationExpiry, 1);
+      const participant = await (program.account as any).participant.fetch(
+        participantPDA
+      );
+      expect(participant.proofDays).to.equal(1);
     });
   });
 
@@ -1326,7 +5282,8 @@ describe("proven-stake", () => {
             longChallengeId,
             new BN(STAKE_AMOUNT),
             TOTAL_DAYS,
-            new BN(getFutureTimestamp(60))
+            new BN(getFutureTimestamp(60)),
+            challengeParams()
           )
           .accountsPartial({
             creator: creator.publicKey,
@@ -1358,7 +5315,8 @@ describe("proven-stake", () => {
             emptyChallengeId,
             new BN(STAKE_AMOUNT),
             TOTAL_DAYS,
-            new BN(getFutureTimestamp(60))
+            new BN(getFutureTimestamp(60)),
+            challengeParams()
           )
           .accountsPartial({
             creator: creator.publicKey,
@@ -1389,7 +5347,8 @@ describe("proven-stake", () => {
             zeroDurationId,
             new BN(STAKE_AMOUNT),
             0, // Zero days
-            new BN(getFutureTimestamp(60))
+            new BN(getFutureTimestamp(60)),
+            challengeParams()
           )
           .accountsPartial({
             creator: creator.publicKey,